@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{GateKey, Tenant};
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct AddGateKey<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GateKey::INIT_SPACE,
+        seeds = [b"gate_key", tenant.key().as_ref(), key.as_ref()],
+        bump
+    )]
+    pub gate_key: Account<'info, GateKey>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddGateKey<'info> {
+    /// Registers a gate device whose signed tickets are accepted at this tenant.
+    pub fn add_gate_key(&mut self, bumps: &AddGateKeyBumps, key: Pubkey) -> Result<()> {
+        self.gate_key.set_inner(GateKey {
+            tenant: self.tenant.key(),
+            key,
+            bump: bumps.gate_key,
+        });
+
+        emit!(GateKeyAdded {
+            tenant: self.tenant.key(),
+            key,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct GateKeyAdded {
+    pub tenant: Pubkey,
+    pub key: Pubkey,
+}
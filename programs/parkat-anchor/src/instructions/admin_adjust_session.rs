@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User};
+
+#[derive(Accounts)]
+pub struct AdminAdjustSession<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Wallet of the parked user, only used to derive the user PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> AdminAdjustSession<'info> {
+    pub fn admin_adjust_session(&mut self, start_time: i64) -> Result<()> {
+        let user_account = &mut self.user_account;
+
+        if !user_account.is_parked {
+            return Err(error!(AdminAdjustSessionError::NotCurrentlyParked));
+        }
+
+        // A session cannot start in the future
+        let current_time = Clock::get()?.unix_timestamp;
+        if start_time > current_time {
+            return Err(error!(AdminAdjustSessionError::InvalidStartTime));
+        }
+
+        // Nor before the user's last exit, which bounds how much an earlier start can add to the fee
+        if start_time < user_account.last_session.end_time {
+            return Err(error!(AdminAdjustSessionError::OverlapsPreviousSession));
+        }
+
+        let previous_start_time = user_account.time_stamp;
+        user_account.time_stamp = start_time;
+        // The recorded slot no longer matches, so the session is billed by wall-clock time
+        user_account.start_slot = 0;
+
+        emit!(SessionAdjusted {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            admin: self.admin.key(),
+            previous_start_time,
+            start_time,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct SessionAdjusted {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub admin: Pubkey,
+    pub previous_start_time: i64,
+    pub start_time: i64,
+}
+
+#[error_code]
+pub enum AdminAdjustSessionError {
+    #[msg("User is not currently parked")]
+    NotCurrentlyParked,
+
+    #[msg("Session start time cannot be in the future")]
+    InvalidStartTime,
+
+    #[msg("Session cannot start before the user's last exit")]
+    OverlapsPreviousSession,
+}
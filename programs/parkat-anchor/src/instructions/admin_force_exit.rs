@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::{
+    rate_tenant, record_roaming_fee, FeeAccounts, ParkingFeeCollected,
+};
+use crate::state::{Fleet, FleetMember, PlatformConfig, Profile, RoamingAgreement, Tenant, User};
+
+#[derive(Accounts)]
+pub struct AdminForceExit<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Wallet of the parked user, only used to derive the user and vault PDAs
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump = tenant.revenue_bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Platform treasury, must match the one in the platform config
+    #[account(
+        mut,
+        address = platform_config.treasury,
+    )]
+    pub platform_treasury: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Fleet paying for this session instead of the user's own vault
+    pub fleet: Option<Account<'info, Fleet>>,
+
+    /// CHECK: Fleet vault, verified against the fleet's stored bump
+    #[account(mut)]
+    pub fleet_vault: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub fleet_member: Option<Account<'info, FleetMember>>,
+
+    /// Cross-tenant profile linked to the user, paying instead of the user's own vault
+    #[account(mut)]
+    pub profile: Option<Account<'info, Profile>>,
+
+    /// CHECK: Profile vault, verified against the profile's stored bump
+    #[account(mut)]
+    pub profile_vault: Option<UncheckedAccount<'info>>,
+
+    /// Tenant visited during a roaming session, whose rates apply
+    pub visited_tenant: Option<Account<'info, Tenant>>,
+
+    /// Agreement recording what `tenant` owes the visited tenant
+    #[account(mut)]
+    pub roaming_agreement: Option<Account<'info, RoamingAgreement>>,
+}
+
+impl<'info> AdminForceExit<'info> {
+    /// Closes a session the gate failed to end. The fee is computed like
+    /// `process_exit` from `end_time` (defaults to now) and paid by the same
+    /// fleet, profile or vault. An explicit fee may only waive part of it.
+    pub fn admin_force_exit(&mut self, end_time: Option<i64>, fee: Option<u64>) -> Result<()> {
+        let user_account = &mut self.user_account;
+
+        if !user_account.is_parked {
+            return Err(error!(AdminForceExitError::NotCurrentlyParked));
+        }
+
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+
+        // The session cannot end before it started or in the future
+        if let Some(end_time) = end_time {
+            if end_time < user_account.time_stamp || end_time > current_time {
+                return Err(error!(AdminForceExitError::InvalidEndTime));
+            }
+        }
+
+        let rate_tenant = rate_tenant(
+            &self.tenant,
+            user_account,
+            self.visited_tenant.as_ref(),
+            self.roaming_agreement.as_deref(),
+        )?;
+
+        // Past end times are measured on wall-clock time, as their slot is unknown
+        let duration = match end_time {
+            Some(end_time) => rate_tenant.session_duration(user_account.time_stamp, 0, end_time, 0),
+            None => rate_tenant.session_duration(
+                user_account.time_stamp,
+                user_account.start_slot,
+                current_time,
+                clock.slot,
+            ),
+        }
+        .ok_or_else(|| error!(AdminForceExitError::InvalidEndTime))?;
+
+        let computed_fee = rate_tenant
+            .parking_fee(duration, user_account.class)
+            .ok_or_else(|| error!(AdminForceExitError::AmountCalculationError))?;
+
+        let amount = match fee {
+            Some(fee) if fee > computed_fee => {
+                return Err(error!(AdminForceExitError::FeeExceedsComputedFee));
+            }
+            Some(fee) => fee,
+            None => computed_fee,
+        };
+
+        let (payer, source, tenant_amount, platform_amount) = FeeAccounts {
+            tenant: &self.tenant,
+            vault: &self.vault,
+            revenue: &self.revenue,
+            platform_config: &self.platform_config,
+            platform_treasury: &self.platform_treasury,
+            system_program: &self.system_program,
+            fleet: self.fleet.as_ref(),
+            fleet_vault: self.fleet_vault.as_ref(),
+            fleet_member: self.fleet_member.as_mut(),
+            profile: self.profile.as_mut(),
+            profile_vault: self.profile_vault.as_ref(),
+        }
+        .pay(user_account, amount, current_time)?;
+
+        emit!(ParkingFeeCollected {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            source,
+            tenant_amount,
+            platform_amount,
+        });
+
+        record_roaming_fee(
+            &mut self.tenant,
+            user_account,
+            self.roaming_agreement.as_deref_mut(),
+            tenant_amount,
+        )?;
+
+        let start_time = user_account.time_stamp;
+        let end_time = end_time.unwrap_or(current_time).max(start_time);
+        user_account.finish_session(end_time, amount, payer, tenant_amount);
+        self.tenant
+            .record_session(tenant_amount)
+            .ok_or_else(|| error!(AdminForceExitError::AmountCalculationError))?;
+
+        emit!(SessionForceClosed {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            admin: self.admin.key(),
+            start_time,
+            end_time,
+            fee: amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct SessionForceClosed {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub admin: Pubkey,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fee: u64,
+}
+
+#[error_code]
+pub enum AdminForceExitError {
+    #[msg("User is not currently parked")]
+    NotCurrentlyParked,
+
+    #[msg("End time must be between the session start and the current time")]
+    InvalidEndTime,
+
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+
+    #[msg("Fee cannot exceed the fee computed for the session")]
+    FeeExceedsComputedFee,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User, UserStatus};
+
+#[derive(Accounts)]
+pub struct ApproveUser<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Wallet of the user being approved, only used to derive the user PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> ApproveUser<'info> {
+    pub fn approve_user(&mut self) -> Result<()> {
+        if self.user_account.status != UserStatus::PendingApproval {
+            return Err(error!(ApproveUserError::NotPendingApproval));
+        }
+
+        self.user_account.status = UserStatus::Active;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum ApproveUserError {
+    #[msg("User is not waiting for approval")]
+    NotPendingApproval,
+}
@@ -3,7 +3,7 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::state::{Tenant, User};
+use crate::state::{PlatformConfig, Tenant, User};
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
@@ -34,34 +34,86 @@ pub struct Deposit<'info> {
     )]
     pub car: Account<'info, User>,
 
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> Deposit<'info> {
     pub fn deposit(&mut self, amount: u64) -> Result<()> {
-        if amount == 0 {
-            return Err(error!(Error::InvalidDepositAmount));
-        }
-
-        // Perform transfer first
-        let cpi_program = self.system_program.to_account_info();
-        let cpi_accounts = Transfer {
-            from: self.user.to_account_info(),
-            to: self.vault.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        
-        transfer(cpi_ctx, amount)?;
-
-        // Update tracked amount to reflect current vault balance
-        self.car.amount = self.vault.lamports();
-
-        Ok(())
+        credit_vault(
+            &self.system_program,
+            self.user.to_account_info(),
+            &self.vault,
+            &mut self.car,
+            &self.tenant,
+            &self.platform_config,
+            amount,
+        )
     }
 }
 
+/// Moves `amount` from `funder` into a user's vault and credits it to the user's ledger.
+pub(crate) fn credit_vault<'info>(
+    system_program: &Program<'info, System>,
+    funder: AccountInfo<'info>,
+    vault: &SystemAccount<'info>,
+    car: &mut User,
+    tenant: &Tenant,
+    platform_config: &PlatformConfig,
+    amount: u64,
+) -> Result<()> {
+    if platform_config.paused || tenant.paused {
+        return Err(error!(DepositError::Paused));
+    }
+
+    if amount == 0 {
+        return Err(error!(DepositError::InvalidDepositAmount));
+    }
+
+    // A vault that was never funded must end up rent exempt
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let new_balance = vault
+        .lamports()
+        .checked_add(amount)
+        .ok_or_else(|| error!(DepositError::ArithmeticOverflow))?;
+    if new_balance < rent_exempt_minimum {
+        return Err(error!(DepositError::DepositBelowRentExemption));
+    }
+
+    // Perform transfer first
+    let cpi_program = system_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: funder,
+        to: vault.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+    transfer(cpi_ctx, amount)?;
+
+    // Update tracked amount to reflect current vault balance
+    car.amount = vault.lamports();
+    car.credit_deposit(amount)
+        .ok_or_else(|| error!(DepositError::ArithmeticOverflow))?;
+
+    Ok(())
+}
+
 #[error_code]
-pub enum Error {
+pub enum DepositError {
     #[msg("Deposit amount must be greater than zero")]
     InvalidDepositAmount,
+
+    #[msg("Parking is paused for this tenant")]
+    Paused,
+
+    #[msg("Amount calculation failed")]
+    ArithmeticOverflow,
+
+    #[msg("Deposit would leave the vault below its rent-exempt minimum")]
+    DepositBelowRentExemption,
 }
\ No newline at end of file
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::deposit_by_user::credit_vault;
+use crate::state::{PlatformConfig, Tenant, User};
+
+#[derive(Accounts)]
+pub struct DepositFor<'info> {
+    /// Parent, employer or sponsor paying into someone else's vault
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet of the user being funded, only used to derive the user and vault PDAs
+    pub beneficiary: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", tenant.key().as_ref(), beneficiary.key().as_ref()],
+        bump = car.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), beneficiary.key().as_ref()],
+        bump = car.state_bump,
+    )]
+    pub car: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositFor<'info> {
+    pub fn deposit_for(&mut self, amount: u64) -> Result<()> {
+        credit_vault(
+            &self.system_program,
+            self.funder.to_account_info(),
+            &self.vault,
+            &mut self.car,
+            &self.tenant,
+            &self.platform_config,
+            amount,
+        )?;
+
+        emit!(VaultFunded {
+            tenant: self.tenant.key(),
+            beneficiary: self.beneficiary.key(),
+            funder: self.funder.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VaultFunded {
+    pub tenant: Pubkey,
+    pub beneficiary: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
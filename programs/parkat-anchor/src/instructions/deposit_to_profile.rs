@@ -0,0 +1,79 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::state::{PlatformConfig, Profile, Tenant};
+
+#[derive(Accounts)]
+pub struct DepositToProfile<'info> {
+    /// Profile owner or anyone topping it up on their behalf
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", profile.owner.as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile_vault", profile.owner.as_ref()],
+        bump = profile.vault_bump,
+    )]
+    pub profile_vault: SystemAccount<'info>,
+
+    /// Tenant the top-up is made through
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositToProfile<'info> {
+    pub fn deposit_to_profile(&mut self, amount: u64) -> Result<()> {
+        if self.platform_config.paused || self.tenant.paused {
+            return Err(error!(DepositToProfileError::Paused));
+        }
+
+        if amount == 0 {
+            return Err(error!(DepositToProfileError::InvalidDepositAmount));
+        }
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.funder.to_account_info(),
+            to: self.profile_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        // Update tracked amount to reflect current vault balance
+        self.profile.amount = self.profile_vault.lamports();
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum DepositToProfileError {
+    #[msg("Deposit amount must be greater than zero")]
+    InvalidDepositAmount,
+    #[msg("Parking is paused for this tenant")]
+    Paused,
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::{PayoutSplit, Tenant};
+
+#[derive(Accounts)]
+pub struct DistributeRevenue<'info> {
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump = tenant.revenue_bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"payout_split", tenant.key().as_ref()],
+        bump = payout_split.bump,
+        has_one = tenant,
+    )]
+    pub payout_split: Account<'info, PayoutSplit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DistributeRevenue<'info> {
+    /// Pays every recipient its share of the collected revenue. Recipient wallets are
+    /// passed as remaining accounts, in the same order as the payout split.
+    pub fn distribute_revenue(&mut self, recipient_wallets: &[AccountInfo<'info>]) -> Result<()> {
+        let recipients = &self.payout_split.recipients;
+
+        if recipient_wallets.len() != recipients.len() {
+            return Err(error!(DistributeRevenueError::RecipientMismatch));
+        }
+
+        // Keep the revenue PDA rent exempt and leave what open disputes and
+        // roaming settlements may still claim
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let distributable = self
+            .revenue
+            .lamports()
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.tenant.reserved_revenue());
+        if distributable == 0 {
+            return Err(error!(DistributeRevenueError::NothingToDistribute));
+        }
+
+        let tenant_key = self.tenant.key();
+        let revenue_seeds: &[&[u8]] = &[
+            b"revenue",
+            tenant_key.as_ref(),
+            &[self.tenant.revenue_bump],
+        ];
+
+        let mut distributed: u64 = 0;
+        for (recipient, wallet) in recipients.iter().zip(recipient_wallets) {
+            if wallet.key() != recipient.wallet {
+                return Err(error!(DistributeRevenueError::RecipientMismatch));
+            }
+
+            let share = recipient
+                .share_of(distributable)
+                .ok_or_else(|| error!(DistributeRevenueError::AmountCalculationError))?;
+            if share == 0 {
+                continue;
+            }
+
+            transfer_from_pda(
+                &self.system_program,
+                self.revenue.to_account_info(),
+                wallet.clone(),
+                revenue_seeds,
+                share,
+            )?;
+
+            distributed = distributed
+                .checked_add(share)
+                .ok_or_else(|| error!(DistributeRevenueError::AmountCalculationError))?;
+        }
+
+        emit!(RevenueDistributed {
+            tenant: tenant_key,
+            amount: distributed,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RevenueDistributed {
+    pub tenant: Pubkey,
+    pub amount: u64,
+}
+
+#[error_code]
+pub enum DistributeRevenueError {
+    #[msg("Recipient accounts do not match the payout split")]
+    RecipientMismatch,
+
+    #[msg("No revenue available to distribute")]
+    NothingToDistribute,
+
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+}
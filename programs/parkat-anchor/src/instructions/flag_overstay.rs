@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User};
+
+#[derive(Accounts)]
+pub struct FlagOverstay<'info> {
+    pub enforcer: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet of the parked user, only used to derive the user PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> FlagOverstay<'info> {
+    pub fn flag_overstay(&mut self) -> Result<()> {
+        let user_account = &mut self.user_account;
+
+        if !user_account.is_parked {
+            return Err(error!(FlagOverstayError::NotCurrentlyParked));
+        }
+
+        if user_account.overstay_flagged_at != 0 {
+            return Err(error!(FlagOverstayError::AlreadyFlagged));
+        }
+
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
+        let duration = self
+            .tenant
+            .session_duration(user_account.time_stamp, user_account.start_slot, current_time, clock.slot)
+            .unwrap_or_default();
+
+        if !self.tenant.is_overstay(duration) {
+            return Err(error!(FlagOverstayError::NotOverstayed));
+        }
+
+        user_account.overstay_flagged_at = current_time;
+
+        emit!(OverstayFlagged {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            enforcer: self.enforcer.key(),
+            start_time: user_account.time_stamp,
+            flagged_at: current_time,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct OverstayFlagged {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub enforcer: Pubkey,
+    pub start_time: i64,
+    pub flagged_at: i64,
+}
+
+#[error_code]
+pub enum FlagOverstayError {
+    #[msg("User is not currently parked")]
+    NotCurrentlyParked,
+
+    #[msg("Session has already been flagged")]
+    AlreadyFlagged,
+
+    #[msg("Session has not exceeded the maximum stay")]
+    NotOverstayed,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::state::{Fleet, PlatformConfig, Tenant};
+
+#[derive(Accounts)]
+pub struct FundFleet<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"fleet", fleet.tenant.as_ref(), fleet.admin.as_ref()],
+        bump = fleet.bump,
+    )]
+    pub fleet: Account<'info, Fleet>,
+
+    #[account(
+        mut,
+        seeds = [b"fleet_vault", fleet.key().as_ref()],
+        bump = fleet.vault_bump,
+    )]
+    pub fleet_vault: SystemAccount<'info>,
+
+    #[account(address = fleet.tenant)]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundFleet<'info> {
+    pub fn fund_fleet(&mut self, amount: u64) -> Result<()> {
+        if self.platform_config.paused || self.tenant.paused {
+            return Err(error!(FundFleetError::Paused));
+        }
+
+        if amount == 0 {
+            return Err(error!(FundFleetError::InvalidDepositAmount));
+        }
+
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.funder.to_account_info(),
+            to: self.fleet_vault.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer(cpi_ctx, amount)?;
+
+        emit!(FleetFunded {
+            fleet: self.fleet.key(),
+            funder: self.funder.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct FleetFunded {
+    pub fleet: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
+
+#[error_code]
+pub enum FundFleetError {
+    #[msg("Deposit amount must be greater than zero")]
+    InvalidDepositAmount,
+    #[msg("Parking is paused for this tenant")]
+    Paused,
+}
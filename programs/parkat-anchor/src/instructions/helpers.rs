@@ -0,0 +1,29 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+/// Zero-extends a program account to `new_len` bytes, topping up its rent from `payer`.
+pub(crate) fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(new_len);
+    let shortfall = rent_exempt_minimum.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_program = system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: payer.to_account_info(),
+            to: account.clone(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        transfer(cpi_ctx, shortfall)?;
+    }
+
+    account.resize(new_len)?;
+
+    Ok(())
+}
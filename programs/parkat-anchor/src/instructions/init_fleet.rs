@@ -0,0 +1,84 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::state::{Fleet, Tenant};
+
+#[derive(Accounts)]
+pub struct InitFleet<'info> {
+    /// Employer administering the fleet
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Fleet::INIT_SPACE,
+        seeds = [b"fleet", tenant.key().as_ref(), admin.key().as_ref()],
+        bump
+    )]
+    pub fleet: Account<'info, Fleet>,
+
+    #[account(
+        mut,
+        seeds = [b"fleet_vault", fleet.key().as_ref()],
+        bump,
+    )]
+    pub fleet_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitFleet<'info> {
+    pub fn init_fleet(&mut self, bumps: &InitFleetBumps, name: String) -> Result<()> {
+        if name.is_empty() {
+            return Err(error!(InitFleetError::EmptyFleetName));
+        }
+
+        let mut name_bytes = [0u8; 32];
+        let input_bytes = name.as_bytes();
+        let len = input_bytes.len().min(32);
+        name_bytes[..len].copy_from_slice(&input_bytes[..len]);
+
+        self.fleet.set_inner(Fleet {
+            admin: self.admin.key(),
+            tenant: self.tenant.key(),
+            name: name_bytes,
+            member_count: 0,
+            bump: bumps.fleet,
+            vault_bump: bumps.fleet_vault,
+        });
+
+        // Fund the fleet vault up to rent exemption so it never drops below it
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.fleet_vault.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.fleet_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_ctx, shortfall)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum InitFleetError {
+    #[msg("Fleet name cannot be empty")]
+    EmptyFleetName,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::set_payout_split::check_recipients;
+use crate::state::{PayoutRecipient, PayoutSplit, Tenant};
+
+#[derive(Accounts)]
+pub struct InitPayoutSplit<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PayoutSplit::INIT_SPACE,
+        seeds = [b"payout_split", tenant.key().as_ref()],
+        bump,
+    )]
+    pub payout_split: Account<'info, PayoutSplit>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitPayoutSplit<'info> {
+    pub fn init_payout_split(
+        &mut self,
+        bumps: &InitPayoutSplitBumps,
+        recipients: Vec<PayoutRecipient>,
+    ) -> Result<()> {
+        check_recipients(&recipients)?;
+
+        self.payout_split.set_inner(PayoutSplit {
+            tenant: self.tenant.key(),
+            recipients,
+            bump: bumps.payout_split,
+        });
+
+        Ok(())
+    }
+}
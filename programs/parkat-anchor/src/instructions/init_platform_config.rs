@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PlatformConfig, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct InitPlatformConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PlatformConfig::INIT_SPACE,
+        seeds = [b"platform"],
+        bump
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::ParkatAnchor>,
+
+    /// Only the program's upgrade authority may set the platform fee
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ InitPlatformConfigError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitPlatformConfig<'info> {
+    pub fn init_platform_config(
+        &mut self,
+        bumps: &InitPlatformConfigBumps,
+        fee_bps: u16,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        if u64::from(fee_bps) > BPS_DENOMINATOR {
+            return Err(error!(InitPlatformConfigError::InvalidFeeBps));
+        }
+
+        let platform_config = &mut self.platform_config;
+        platform_config.authority = self.authority.key();
+        platform_config.fee_bps = fee_bps;
+        platform_config.treasury = treasury;
+        platform_config.bump = bumps.platform_config;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum InitPlatformConfigError {
+    #[msg("Platform fee cannot exceed 10000 basis points")]
+    InvalidFeeBps,
+    #[msg("Only the program's upgrade authority can create the platform config")]
+    Unauthorized,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::state::Profile;
+
+#[derive(Accounts)]
+pub struct InitProfile<'info> {
+    pub user: Signer<'info>,
+
+    /// Pays rent for the profile and its vault
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Profile::INIT_SPACE,
+        seeds = [b"profile", user.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile_vault", user.key().as_ref()],
+        bump,
+    )]
+    pub profile_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitProfile<'info> {
+    pub fn init_profile(&mut self, bumps: &InitProfileBumps, default_plate: String) -> Result<()> {
+        if default_plate.is_empty() {
+            return Err(error!(InitProfileError::EmptyNumberPlate));
+        }
+
+        let mut plate_bytes = [0u8; 16];
+        let input_bytes = default_plate.as_bytes();
+        let len = input_bytes.len().min(16);
+        plate_bytes[..len].copy_from_slice(&input_bytes[..len]);
+
+        // Fund the profile vault up to rent exemption so it never drops below it
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.profile_vault.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.payer.to_account_info(),
+                to: self.profile_vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_ctx, shortfall)?;
+        }
+
+        self.profile.set_inner(Profile {
+            owner: self.user.key(),
+            default_plate: plate_bytes,
+            amount: self.profile_vault.lamports(),
+            bump: bumps.profile,
+            vault_bump: bumps.profile_vault,
+        });
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum InitProfileError {
+    #[msg("Number plate cannot be empty")]
+    EmptyNumberPlate,
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RoamingAgreement, Tenant, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct InitRoamingAgreement<'info> {
+    #[account(mut)]
+    pub admin_a: Signer<'info>,
+
+    pub admin_b: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin_a.key().as_ref()],
+        bump = tenant_a.bump,
+    )]
+    pub tenant_a: Account<'info, Tenant>,
+
+    #[account(
+        seeds = [b"tenant", admin_b.key().as_ref()],
+        bump = tenant_b.bump,
+    )]
+    pub tenant_b: Account<'info, Tenant>,
+
+    #[account(
+        init,
+        payer = admin_a,
+        space = 8 + RoamingAgreement::INIT_SPACE,
+        seeds = [b"roaming", tenant_a.key().as_ref(), tenant_b.key().as_ref()],
+        bump,
+    )]
+    pub roaming_agreement: Account<'info, RoamingAgreement>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitRoamingAgreement<'info> {
+    /// Both tenant admins sign; tenants must be passed in ascending key order.
+    pub fn init_roaming_agreement(
+        &mut self,
+        bumps: &InitRoamingAgreementBumps,
+        settlement_fee_bps: u16,
+    ) -> Result<()> {
+        if self.tenant_a.key() >= self.tenant_b.key() {
+            return Err(error!(InitRoamingAgreementError::UnorderedTenants));
+        }
+
+        if u64::from(settlement_fee_bps) > BPS_DENOMINATOR {
+            return Err(error!(InitRoamingAgreementError::InvalidSettlementFee));
+        }
+
+        self.roaming_agreement.set_inner(RoamingAgreement {
+            tenant_a: self.tenant_a.key(),
+            tenant_b: self.tenant_b.key(),
+            settlement_fee_bps,
+            a_owes_b: 0,
+            b_owes_a: 0,
+            last_settled_at: Clock::get()?.unix_timestamp,
+            bump: bumps.roaming_agreement,
+        });
+
+        emit!(RoamingAgreementCreated {
+            tenant_a: self.tenant_a.key(),
+            tenant_b: self.tenant_b.key(),
+            settlement_fee_bps,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RoamingAgreementCreated {
+    pub tenant_a: Pubkey,
+    pub tenant_b: Pubkey,
+    pub settlement_fee_bps: u16,
+}
+
+#[error_code]
+pub enum InitRoamingAgreementError {
+    #[msg("Tenant A must have the smaller key")]
+    UnorderedTenants,
+
+    #[msg("Settlement fee cannot exceed 10_000 basis points")]
+    InvalidSettlementFee,
+}
@@ -1,6 +1,9 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 
-use crate::state::Tenant;
+use crate::state::{Tenant, TENANT_VERSION};
 
 #[derive(Accounts)]
 pub struct InitTenant<'info> {
@@ -16,6 +19,13 @@ pub struct InitTenant<'info> {
     )]
     pub tenant: Account<'info, Tenant>,
 
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -31,6 +41,8 @@ impl<'info> InitTenant<'info> {
         tenant.admin = self.admin.key();
         tenant.created_at = Clock::get()?.unix_timestamp;
         tenant.bump = bumps.tenant;
+        tenant.revenue_bump = bumps.revenue;
+        tenant.version = TENANT_VERSION;
 
         // Convert tenant_name(String) to bytes with length validation
         let mut name_bytes = [0u8; 32];
@@ -39,6 +51,20 @@ impl<'info> InitTenant<'info> {
         name_bytes[..len].copy_from_slice(&input_bytes[..len]);
         tenant.name = name_bytes;
 
+        // Fund the revenue PDA up to rent exemption so it can receive small fees
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.revenue.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.admin.to_account_info(),
+                to: self.revenue.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_ctx, shortfall)?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PlatformConfig, TenantRegistry};
+
+#[derive(Accounts)]
+pub struct InitTenantRegistry<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+        has_one = authority,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TenantRegistry::space(0),
+        seeds = [b"registry"],
+        bump
+    )]
+    pub tenant_registry: Account<'info, TenantRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTenantRegistry<'info> {
+    pub fn init_tenant_registry(&mut self, bumps: &InitTenantRegistryBumps) -> Result<()> {
+        let tenant_registry = &mut self.tenant_registry;
+        tenant_registry.tenants = Vec::new();
+        tenant_registry.bump = bumps.tenant_registry;
+
+        Ok(())
+    }
+}
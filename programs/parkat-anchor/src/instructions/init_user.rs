@@ -1,11 +1,17 @@
-use anchor_lang::prelude::*;
-use crate::state::{Tenant, User};
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use crate::state::{AdmissionMode, PlatformConfig, Profile, Tenant, User, UserStatus, USER_VERSION};
 
 #[derive(Accounts)]
 pub struct InitUser<'info> {
-    #[account(mut)]
     pub user: Signer<'info>,
 
+    /// Pays rent for the user account, e.g. a corporate sponsor or tenant relayer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     #[account(
         mut,
         seeds = [b"tenant", tenant_admin.key().as_ref()],
@@ -18,28 +24,78 @@ pub struct InitUser<'info> {
 
     #[account(
         init,
-        payer = user,
+        payer = payer,
         space = 8 + User::INIT_SPACE,
         seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
         bump,
     )]
     pub user_account: Account<'info, User>,
 
+    /// Pre-funded to rent exemption by the payer
     #[account(
+        mut,
         seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
         bump,
     )]
     pub vault: SystemAccount<'info>,
 
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
     pub system_program: Program<'info, System>,
+
+    /// Existing cross-tenant profile to link, supplying the default plate
+    #[account(
+        seeds = [b"profile", user.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Option<Account<'info, Profile>>,
 }
 
 impl<'info> InitUser<'info> {
-    pub fn init_user(&mut self, bumps: &InitUserBumps, number_plate: String) -> Result<()> {
-        if number_plate.is_empty() {
-            return Err(error!(Error::EmptyNumberPlate));
+    pub fn init_user(
+        &mut self,
+        bumps: &InitUserBumps,
+        number_plate: String,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        if self.platform_config.paused || self.tenant.paused {
+            return Err(error!(InitUserError::Paused));
         }
 
+        if self.profile.is_some() && !self.tenant.accepts_profiles {
+            return Err(error!(InitUserError::ProfilesNotAccepted));
+        }
+
+        // Convert number plate string to fixed-length byte array, falling back
+        // to the profile's default plate
+        let plate_bytes = if !number_plate.is_empty() {
+            let mut plate_bytes = [0u8; 16];
+            let input_bytes = number_plate.as_bytes();
+            let len = input_bytes.len().min(16);
+            plate_bytes[..len].copy_from_slice(&input_bytes[..len]);
+            plate_bytes
+        } else if let Some(profile) = &self.profile {
+            profile.default_plate
+        } else {
+            return Err(error!(InitUserError::EmptyNumberPlate));
+        };
+
+        // Apply the tenant's admission policy
+        let status = match self.tenant.admission_mode {
+            AdmissionMode::Open => UserStatus::Active,
+            AdmissionMode::ApprovalRequired => UserStatus::PendingApproval,
+            AdmissionMode::Allowlist => {
+                if !self.tenant.is_allowlisted(&self.user.key(), &proof) {
+                    return Err(error!(InitUserError::NotAllowlisted));
+                }
+                UserStatus::Active
+            }
+        };
+
         let user_account = &mut self.user_account;
         user_account.user = self.user.key();
         user_account.tenant = self.tenant.key();
@@ -48,20 +104,42 @@ impl<'info> InitUser<'info> {
         user_account.amount = 0;
         user_account.vault_bump = bumps.vault;
         user_account.state_bump = bumps.user_account;
-
-        // Convert number plate string to fixed-length byte array
-        let mut plate_bytes = [0u8; 16];
-        let input_bytes = number_plate.as_bytes();
-        let len = input_bytes.len().min(16);
-        plate_bytes[..len].copy_from_slice(&input_bytes[..len]);
+        user_account.status = status;
+        user_account.version = USER_VERSION;
         user_account.number_plate = plate_bytes;
+        user_account.profile = self.profile.as_ref().map(|profile| profile.key()).unwrap_or_default();
+
+        // Fund the vault up to rent exemption so deposits and fees of any size
+        // leave it in a valid state. The reserve is not part of the user's balance.
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.vault.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.payer.to_account_info(),
+                to: self.vault.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_ctx, shortfall)?;
+        }
+        self.user_account.amount = self.vault.lamports();
 
         Ok(())
     }
 }
 
 #[error_code]
-pub enum Error {
+pub enum InitUserError {
     #[msg("Number plate cannot be empty")]
     EmptyNumberPlate,
+
+    #[msg("Parking is paused for this tenant")]
+    Paused,
+
+    #[msg("User is not on the tenant allowlist")]
+    NotAllowlisted,
+
+    #[msg("Tenant does not accept profile payments")]
+    ProfilesNotAccepted,
 }
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Receipt, Tenant, User};
+
+#[derive(Accounts)]
+pub struct IssueReceipt<'info> {
+    pub user: Signer<'info>,
+
+    /// Pays rent for the receipt account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Receipt::INIT_SPACE,
+        seeds = [
+            b"receipt",
+            user_account.key().as_ref(),
+            user_account.session_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt: Account<'info, Receipt>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IssueReceipt<'info> {
+    /// Issues a receipt for the user's most recently finalized session.
+    pub fn issue_receipt(&mut self, bumps: &IssueReceiptBumps) -> Result<()> {
+        let user_account = &self.user_account;
+
+        if user_account.session_count == 0 {
+            return Err(error!(IssueReceiptError::NoFinalizedSession));
+        }
+
+        let mut receipt = Receipt {
+            tenant: self.tenant.key(),
+            user: self.user.key(),
+            user_account: user_account.key(),
+            tenant_name: self.tenant.name,
+            number_plate: user_account.number_plate,
+            start_time: user_account.last_session.start_time,
+            end_time: user_account.last_session.end_time,
+            fee: user_account.last_session.fee,
+            receipt_number: user_account.session_count,
+            commitment: [0u8; 32],
+            issued_at: Clock::get()?.unix_timestamp,
+            bump: bumps.receipt,
+        };
+        receipt.commitment = receipt.compute_commitment();
+
+        emit!(ReceiptIssued {
+            tenant: receipt.tenant,
+            user: receipt.user,
+            receipt_number: receipt.receipt_number,
+            fee: receipt.fee,
+            commitment: receipt.commitment,
+        });
+
+        self.receipt.set_inner(receipt);
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ReceiptIssued {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub receipt_number: u64,
+    pub fee: u64,
+    pub commitment: [u8; 32],
+}
+
+#[error_code]
+pub enum IssueReceiptError {
+    #[msg("User has no finalized session to receipt")]
+    NoFinalizedSession,
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Profile, Tenant, User};
+
+#[derive(Accounts)]
+pub struct LinkProfile<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        seeds = [b"profile", user.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, Profile>,
+}
+
+impl<'info> LinkProfile<'info> {
+    pub fn link_profile(&mut self) -> Result<()> {
+        if !self.tenant.accepts_profiles {
+            return Err(error!(LinkProfileError::ProfilesNotAccepted));
+        }
+
+        self.user_account.profile = self.profile.key();
+
+        emit!(ProfileLinkChanged {
+            tenant: self.tenant.key(),
+            user: self.user.key(),
+            profile: self.profile.key(),
+            linked: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn unlink_profile(&mut self) -> Result<()> {
+        self.user_account.profile = Pubkey::default();
+
+        emit!(ProfileLinkChanged {
+            tenant: self.tenant.key(),
+            user: self.user.key(),
+            profile: self.profile.key(),
+            linked: false,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct ProfileLinkChanged {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub profile: Pubkey,
+    pub linked: bool,
+}
+
+#[error_code]
+pub enum LinkProfileError {
+    #[msg("Tenant does not accept profile payments")]
+    ProfilesNotAccepted,
+}
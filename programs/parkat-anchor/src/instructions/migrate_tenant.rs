@@ -0,0 +1,105 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::instructions::helpers::grow_account;
+use crate::state::{Tenant, TENANT_VERSION};
+
+/// Size of the original, unversioned `Tenant` (admin, name, created_at, bump)
+const TENANT_V1_SPACE: usize = 32 + 32 + 8 + 1;
+
+#[derive(Accounts)]
+pub struct MigrateTenant<'info> {
+    /// Pays the rent for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Tenant in an unversioned layout, which `Account` cannot deserialize.
+    /// Discriminator and PDA are verified in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub tenant: UncheckedAccount<'info>,
+
+    /// Revenue PDA, which v1 tenants never recorded a bump for
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateTenant<'info> {
+    /// Grows an unversioned tenant to the current layout. Fields added since
+    /// v1 start at their zero defaults, except the revenue bump, which is
+    /// derived here so fees can be collected after the migration.
+    pub fn migrate_tenant_v1_to_v2(&mut self, bumps: &MigrateTenantBumps) -> Result<()> {
+        let tenant_info = self.tenant.to_account_info();
+        let new_len = 8 + Tenant::INIT_SPACE;
+
+        {
+            let data = tenant_info.try_borrow_data()?;
+            if data.len() < 8 + TENANT_V1_SPACE || data[..8] != *Tenant::DISCRIMINATOR {
+                return Err(error!(MigrateTenantError::InvalidTenant));
+            }
+            if data.len() >= new_len {
+                return Err(error!(MigrateTenantError::AlreadyMigrated));
+            }
+
+            // v1 layout: admin at 8..40, bump at 80
+            let expected = Pubkey::create_program_address(
+                &[b"tenant", &data[8..40], &[data[80]]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(MigrateTenantError::InvalidTenant))?;
+            if expected != tenant_info.key() {
+                return Err(error!(MigrateTenantError::InvalidTenant));
+            }
+        }
+
+        grow_account(&tenant_info, &self.payer, &self.system_program, new_len)?;
+
+        let mut tenant = Tenant::try_deserialize(&mut &tenant_info.try_borrow_data()?[..])?;
+        tenant.revenue_bump = bumps.revenue;
+        tenant.version = TENANT_VERSION;
+        tenant.try_serialize(&mut &mut tenant_info.try_borrow_mut_data()?[..])?;
+
+        // Fund the revenue PDA up to rent exemption so it can receive small fees
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let shortfall = rent_exempt_minimum.saturating_sub(self.revenue.lamports());
+        if shortfall > 0 {
+            let cpi_program = self.system_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: self.payer.to_account_info(),
+                to: self.revenue.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+            transfer(cpi_ctx, shortfall)?;
+        }
+
+        emit!(TenantMigrated {
+            tenant: tenant_info.key(),
+            version: TENANT_VERSION,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TenantMigrated {
+    pub tenant: Pubkey,
+    pub version: u8,
+}
+
+#[error_code]
+pub enum MigrateTenantError {
+    #[msg("Account is not a tenant of this program")]
+    InvalidTenant,
+
+    #[msg("Tenant already uses the current layout")]
+    AlreadyMigrated,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::instructions::helpers::grow_account;
+use crate::state::{User, USER_VERSION};
+
+/// Size of the original, unversioned `User`
+/// (user, tenant, time_stamp, is_parked, amount, vault_bump, state_bump, number_plate)
+const USER_V1_SPACE: usize = 32 + 32 + 8 + 1 + 8 + 1 + 1 + 16;
+
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    /// Pays the rent for the extra space and the vault's rent-exempt reserve
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: User in an unversioned layout, which `Account` cannot deserialize.
+    /// Discriminator and PDA are verified in the handler.
+    #[account(mut, owner = crate::ID)]
+    pub user_account: UncheckedAccount<'info>,
+
+    /// The user's vault, whose balance seeds the ledger. Verified in the handler.
+    #[account(mut)]
+    pub vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUser<'info> {
+    /// Grows an unversioned user account to the current layout. Fields added
+    /// since v1 start at their zero defaults, except the ledger, which is
+    /// seeded from the vault so the user can still spend and withdraw. The
+    /// payer adds the vault's rent-exempt reserve, which v1 never set aside.
+    pub fn migrate_user_v1_to_v2(&mut self) -> Result<()> {
+        let user_info = self.user_account.to_account_info();
+        let new_len = 8 + User::INIT_SPACE;
+
+        {
+            let data = user_info.try_borrow_data()?;
+            if data.len() < 8 + USER_V1_SPACE || data[..8] != *User::DISCRIMINATOR {
+                return Err(error!(MigrateUserError::InvalidUser));
+            }
+            if data.len() >= new_len {
+                return Err(error!(MigrateUserError::AlreadyMigrated));
+            }
+
+            // v1 layout: user at 8..40, tenant at 40..72, state_bump at 90
+            let expected = Pubkey::create_program_address(
+                &[b"user", &data[40..72], &data[8..40], &[data[90]]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(MigrateUserError::InvalidUser))?;
+            if expected != user_info.key() {
+                return Err(error!(MigrateUserError::InvalidUser));
+            }
+
+            // v1 layout: vault_bump at 89
+            let expected_vault = Pubkey::create_program_address(
+                &[b"vault", &data[40..72], &data[8..40], &[data[89]]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(MigrateUserError::InvalidVault))?;
+            if expected_vault != self.vault.key() {
+                return Err(error!(MigrateUserError::InvalidVault));
+            }
+        }
+
+        grow_account(&user_info, &self.payer, &self.system_program, new_len)?;
+
+        let mut user_account = User::try_deserialize(&mut &user_info.try_borrow_data()?[..])?;
+        user_account.version = USER_VERSION;
+
+        // v1 vaults were never pre-funded, so their whole balance was deposited by the user
+        let vault_balance = self.vault.lamports();
+        user_account.total_deposited = vault_balance;
+        user_account.available_balance = vault_balance;
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+        transfer(CpiContext::new(cpi_program, cpi_accounts), rent_exempt_minimum)?;
+
+        user_account.amount = self.vault.lamports();
+        user_account.try_serialize(&mut &mut user_info.try_borrow_mut_data()?[..])?;
+
+        emit!(UserMigrated {
+            user_account: user_info.key(),
+            version: USER_VERSION,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct UserMigrated {
+    pub user_account: Pubkey,
+    pub version: u8,
+}
+
+#[error_code]
+pub enum MigrateUserError {
+    #[msg("Account is not a user account of this program")]
+    InvalidUser,
+
+    #[msg("User account already uses the current layout")]
+    AlreadyMigrated,
+
+    #[msg("Vault does not belong to this user account")]
+    InvalidVault,
+}
@@ -3,9 +3,98 @@ pub mod init_user;
 pub mod deposit_by_user;
 pub mod record_parking_start;
 pub mod process_exit;
+pub mod set_user_class;
+pub mod admin_force_exit;
+pub mod admin_adjust_session;
+pub mod set_overstay_policy;
+pub mod flag_overstay;
+pub mod open_dispute;
+pub mod resolve_dispute;
+pub mod init_platform_config;
+pub mod init_payout_split;
+pub mod set_payout_split;
+pub mod distribute_revenue;
+pub mod withdraw_revenue;
+pub mod set_tenant_paused;
+pub mod set_platform_paused;
+pub mod withdraw_by_user;
+pub mod init_tenant_registry;
+pub mod verify_tenant;
+pub mod set_admission_mode;
+pub mod approve_user;
+pub mod suspend_user;
+pub mod set_delegate;
+pub mod deposit_for_user;
+pub mod init_fleet;
+pub mod fund_fleet;
+pub mod withdraw_fleet;
+pub mod set_fleet_member;
+pub mod remove_fleet_member;
+pub mod init_profile;
+pub mod deposit_to_profile;
+pub mod withdraw_from_profile;
+pub mod set_accepts_profiles;
+pub mod link_profile;
+pub mod init_roaming_agreement;
+pub mod settle_roaming;
+pub mod issue_receipt;
+pub mod verify_receipt;
+pub mod migrate_tenant;
+pub mod migrate_user;
+pub mod reconcile_vault;
+pub mod set_rounding_mode;
+pub mod set_timing_policy;
+pub mod add_gate_key;
+pub mod remove_gate_key;
+pub mod set_operator;
+pub(crate) mod helpers;
 
 pub use init_tenant::*;
 pub use init_user::*;
 pub use deposit_by_user::*;
 pub use record_parking_start::*;
 pub use process_exit::*;
+pub use set_user_class::*;
+pub use admin_force_exit::*;
+pub use admin_adjust_session::*;
+pub use set_overstay_policy::*;
+pub use flag_overstay::*;
+pub use open_dispute::*;
+pub use resolve_dispute::*;
+pub use init_platform_config::*;
+pub use init_payout_split::*;
+pub use set_payout_split::*;
+pub use distribute_revenue::*;
+pub use withdraw_revenue::*;
+pub use set_tenant_paused::*;
+pub use set_platform_paused::*;
+pub use withdraw_by_user::*;
+pub use init_tenant_registry::*;
+pub use verify_tenant::*;
+pub use set_admission_mode::*;
+pub use approve_user::*;
+pub use suspend_user::*;
+pub use set_delegate::*;
+pub use deposit_for_user::*;
+pub use init_fleet::*;
+pub use fund_fleet::*;
+pub use withdraw_fleet::*;
+pub use set_fleet_member::*;
+pub use remove_fleet_member::*;
+pub use init_profile::*;
+pub use deposit_to_profile::*;
+pub use withdraw_from_profile::*;
+pub use set_accepts_profiles::*;
+pub use link_profile::*;
+pub use init_roaming_agreement::*;
+pub use settle_roaming::*;
+pub use issue_receipt::*;
+pub use verify_receipt::*;
+pub use migrate_tenant::*;
+pub use migrate_user::*;
+pub use reconcile_vault::*;
+pub use set_rounding_mode::*;
+pub use set_timing_policy::*;
+pub use add_gate_key::*;
+pub use remove_gate_key::*;
+pub use set_operator::*;
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Dispute, DisputeStatus, Tenant, User};
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    pub user: Signer<'info>,
+
+    /// Pays rent for the dispute account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Dispute::INIT_SPACE,
+        seeds = [
+            b"dispute",
+            user_account.key().as_ref(),
+            user_account.session_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenDispute<'info> {
+    /// Disputes the user's most recently finalized session.
+    pub fn open_dispute(&mut self, bumps: &OpenDisputeBumps, reason: String) -> Result<()> {
+        let user_account = &self.user_account;
+
+        if user_account.session_count == 0 {
+            return Err(error!(OpenDisputeError::NoFinalizedSession));
+        }
+
+        if user_account.last_session.fee == 0 {
+            return Err(error!(OpenDisputeError::NothingToDispute));
+        }
+
+        let dispute = &mut self.dispute;
+        dispute.tenant = self.tenant.key();
+        dispute.user = self.user.key();
+        dispute.session_id = user_account.session_count;
+        dispute.fee = user_account.last_session.fee;
+        dispute.payer = user_account.last_fee_payer;
+        dispute.tenant_amount = user_account.last_tenant_amount;
+        dispute.refunded = 0;
+        dispute.status = DisputeStatus::Open;
+        dispute.opened_at = Clock::get()?.unix_timestamp;
+        dispute.resolved_at = 0;
+        dispute.bump = bumps.dispute;
+
+        // Hold the tenant's share back from payouts until the dispute is resolved
+        self.tenant.disputed_amount = self
+            .tenant
+            .disputed_amount
+            .checked_add(dispute.tenant_amount)
+            .ok_or_else(|| error!(OpenDisputeError::AmountCalculationError))?;
+
+        // Convert reason to a fixed-length byte array
+        let mut reason_bytes = [0u8; 64];
+        let input_bytes = reason.as_bytes();
+        let len = input_bytes.len().min(64);
+        reason_bytes[..len].copy_from_slice(&input_bytes[..len]);
+        dispute.reason = reason_bytes;
+
+        emit!(DisputeOpened {
+            tenant: dispute.tenant,
+            user: dispute.user,
+            session_id: dispute.session_id,
+            fee: dispute.fee,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DisputeOpened {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub session_id: u64,
+    pub fee: u64,
+}
+
+#[error_code]
+pub enum OpenDisputeError {
+    #[msg("User has no finalized parking session")]
+    NoFinalizedSession,
+
+    #[msg("Session was not charged a fee")]
+    NothingToDispute,
+
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+}
@@ -3,23 +3,27 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::state::{User, Tenant};
+use crate::instructions::record_parking_start::{GateTicketAccepted, SessionBackdated};
+use crate::state::{
+    FeePayer, Fleet, FleetMember, GateDirection, GateKey, GateTicket, PlatformConfig, Profile,
+    RoamingAgreement, User, Tenant,
+};
 
 #[derive(Accounts)]
 pub struct ProcessExit<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// Owner of the user account or its delegate
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
+        seeds = [b"vault", tenant.key().as_ref(), user_account.user.as_ref()],
         bump = user_account.vault_bump,
     )]
     pub vault: SystemAccount<'info>,
 
     #[account(
         mut,
-        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        seeds = [b"user", tenant.key().as_ref(), user_account.user.as_ref()],
         bump = user_account.state_bump,
     )]
     pub user_account: Account<'info, User>,
@@ -34,14 +38,66 @@ pub struct ProcessExit<'info> {
     /// CHECK: Tenant admin - must match the admin used during tenant initialization
     pub tenant_admin: UncheckedAccount<'info>,
 
-    /// CHECK: Admin wallet only used for transfer
-    pub admin_wallet: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump = tenant.revenue_bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// CHECK: Platform treasury, must match the one in the platform config
+    #[account(
+        mut,
+        address = platform_config.treasury,
+    )]
+    pub platform_treasury: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Fleet paying for this session instead of the user's own vault
+    pub fleet: Option<Account<'info, Fleet>>,
+
+    /// CHECK: Fleet vault, verified against the fleet's stored bump
+    #[account(mut)]
+    pub fleet_vault: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub fleet_member: Option<Account<'info, FleetMember>>,
+
+    /// Cross-tenant profile linked to the user, paying instead of the user's own vault
+    #[account(mut)]
+    pub profile: Option<Account<'info, Profile>>,
+
+    /// CHECK: Profile vault, verified against the profile's stored bump
+    #[account(mut)]
+    pub profile_vault: Option<UncheckedAccount<'info>>,
+
+    /// Tenant visited during a roaming session, whose rates apply
+    pub visited_tenant: Option<Account<'info, Tenant>>,
+
+    /// Agreement recording what `tenant` owes the visited tenant
+    #[account(mut)]
+    pub roaming_agreement: Option<Account<'info, RoamingAgreement>>,
+
+    /// Gate device that signed an exit ticket, registered with the lot's tenant
+    pub gate_key: Option<Account<'info, GateKey>>,
+
+    /// CHECK: Instructions sysvar holding the Ed25519 verification of the ticket
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Tenant operator co-signing an explicit end time
+    pub operator: Option<Signer<'info>>,
 }
 
 impl<'info> ProcessExit<'info> {
-    pub fn process_exit(&mut self) -> Result<()> {
+    pub fn process_exit(&mut self, timestamp: Option<i64>) -> Result<()> {
         let user_account = &mut self.user_account;
 
         // Ensure the user is parked
@@ -50,66 +106,414 @@ impl<'info> ProcessExit<'info> {
         }
 
         // Get current blockchain time
-        let current_time = Clock::get()?.unix_timestamp;
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
 
-        // Calculate duration parked
-        let duration = current_time
-            .checked_sub(user_account.time_stamp)
-            .ok_or_else(|| error!(ProcessExitError::InvalidParkingDuration))?;
+        let authority = self.authority.key();
+        if !user_account.is_authorized(&authority, current_time) {
+            return Err(error!(ProcessExitError::Unauthorized));
+        }
 
-        let duration_u64 = u64::try_from(duration)
-            .map_err(|_| error!(ProcessExitError::InvalidParkingDuration))?;
+        // Roaming sessions are charged at the visited tenant's rates
+        let rate_tenant = rate_tenant(
+            &self.tenant,
+            user_account,
+            self.visited_tenant.as_ref(),
+            self.roaming_agreement.as_deref(),
+        )?;
+
+        // A signed gate ticket ends the session when the car actually left
+        let ticket_time = match (&self.gate_key, &self.instructions_sysvar) {
+            (Some(gate_key), Some(instructions_sysvar)) => {
+                let lot_tenant = rate_tenant.key();
+                let ticket = GateTicket::load(instructions_sysvar, &gate_key.key)?
+                    .filter(|ticket| {
+                        gate_key.tenant == lot_tenant
+                            && ticket.is_valid_for(
+                                &lot_tenant,
+                                &user_account.number_plate,
+                                GateDirection::Exit,
+                                current_time,
+                            )
+                    })
+                    .ok_or_else(|| error!(ProcessExitError::InvalidGateTicket))?;
+
+                emit!(GateTicketAccepted {
+                    tenant: lot_tenant,
+                    user: user_account.user,
+                    gate: gate_key.key,
+                    direction: ticket.direction,
+                    timestamp: ticket.timestamp,
+                });
+
+                Some(ticket.timestamp)
+            }
+            (None, None) => None,
+            _ => return Err(error!(ProcessExitError::InvalidGateTicket)),
+        };
+
+        // An explicit end time is only honoured when the tenant's operator co-signs
+        let backdated_time = match timestamp {
+            Some(timestamp) => {
+                let operator = self
+                    .operator
+                    .as_ref()
+                    .filter(|operator| rate_tenant.is_operator(&operator.key()))
+                    .ok_or_else(|| error!(ProcessExitError::OperatorSignatureRequired))?;
+
+                if !rate_tenant.is_within_backdate_window(timestamp, current_time) {
+                    return Err(error!(ProcessExitError::BackdateOutOfWindow));
+                }
+
+                emit!(SessionBackdated {
+                    tenant: rate_tenant.key(),
+                    user: user_account.user,
+                    operator: operator.key(),
+                    direction: GateDirection::Exit,
+                    timestamp,
+                    recorded_at: current_time,
+                });
+
+                Some(timestamp)
+            }
+            None => None,
+        };
+
+        let exit_time = match (ticket_time, backdated_time) {
+            (Some(_), Some(_)) => return Err(error!(ProcessExitError::ConflictingTimestamps)),
+            (Some(exit_time), None) | (None, Some(exit_time)) => Some(exit_time),
+            (None, None) => None,
+        };
+
+        // Calculate duration parked, tolerating small clock skew between validators.
+        // Past exits are measured on wall-clock time, as the exit slot is unknown,
+        // and come from a signed source, so they may never precede the start.
+        let duration_u64 = match exit_time {
+            Some(exit_time) if exit_time < user_account.time_stamp => {
+                return Err(error!(ProcessExitError::InvalidParkingDuration));
+            }
+            Some(exit_time) => rate_tenant.session_duration(user_account.time_stamp, 0, exit_time, 0),
+            None => rate_tenant.session_duration(
+                user_account.time_stamp,
+                user_account.start_slot,
+                current_time,
+                clock.slot,
+            ),
+        }
+        .ok_or_else(|| error!(ProcessExitError::InvalidParkingDuration))?;
 
         // Calculate amount to transfer (parking fee)
-        // Fee: 100 lamports per minute
-        let rate_per_minute: u64 = 100;
-        let duration_minutes = duration_u64 / 60;
-        let amount = duration_minutes
-            .checked_mul(rate_per_minute)
+        let amount = rate_tenant
+            .parking_fee(duration_u64, user_account.class)
             .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
 
-        // Only transfer if amount > 0
-        if amount > 0 {
-            // Ensure vault has enough balance
-            let vault_balance = self.vault.to_account_info().lamports();
-            if amount > vault_balance {
-                return Err(error!(ProcessExitError::InsufficientVaultBalance));
+        // Delegates can only spend up to their limit
+        if authority != user_account.user {
+            let delegate_spent = user_account
+                .delegate_spent
+                .checked_add(amount)
+                .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
+            if delegate_spent > user_account.delegate_spend_limit {
+                return Err(error!(ProcessExitError::DelegateSpendLimitExceeded));
             }
+            user_account.delegate_spent = delegate_spent;
+        }
 
-            // Prepare signer seeds for vault PDA
-            let tenant_key = self.tenant.key();
-            let user_key = self.user.key();
-
-            let seeds_slice: &[&[u8]] = &[
-                b"vault",
-                tenant_key.as_ref(),
-                user_key.as_ref(),
-                &[user_account.vault_bump],
-            ];
-            let signer_seeds: &[&[&[u8]]] = &[seeds_slice];
-
-            // Perform CPI transfer (parking fee to admin)
-            let cpi_program = self.system_program.to_account_info();
-            let cpi_accounts = Transfer {
-                from: self.vault.to_account_info(),
-                to: self.admin_wallet.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
-
-            transfer(cpi_ctx, amount)?;
-
-            // Update user account amount to reflect current vault balance after transfer
-            user_account.amount = self.vault.lamports();
+        let (payer, source, tenant_amount, platform_amount) = FeeAccounts {
+            tenant: &self.tenant,
+            vault: &self.vault,
+            revenue: &self.revenue,
+            platform_config: &self.platform_config,
+            platform_treasury: &self.platform_treasury,
+            system_program: &self.system_program,
+            fleet: self.fleet.as_ref(),
+            fleet_vault: self.fleet_vault.as_ref(),
+            fleet_member: self.fleet_member.as_mut(),
+            profile: self.profile.as_mut(),
+            profile_vault: self.profile_vault.as_ref(),
         }
+        .pay(user_account, amount, current_time)?;
+
+        emit!(ParkingFeeCollected {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            source,
+            tenant_amount,
+            platform_amount,
+        });
+
+        record_roaming_fee(
+            &mut self.tenant,
+            user_account,
+            self.roaming_agreement.as_deref_mut(),
+            tenant_amount,
+        )?;
 
         // Update parking state
-        user_account.time_stamp = Clock::get()?.unix_timestamp;
-        user_account.is_parked = false;
+        // A session ending within the skew tolerance never ends before it started
+        let end_time = exit_time.unwrap_or(current_time).max(user_account.time_stamp);
+        user_account.finish_session(end_time, amount, payer, tenant_amount);
+        self.tenant
+            .record_session(tenant_amount)
+            .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
 
         Ok(())
     }
 }
 
+/// Tenant whose rates apply to the user's session: the visited tenant while roaming
+/// under an agreement with the home `tenant`, otherwise `tenant` itself.
+pub(crate) fn rate_tenant<'a, 'info>(
+    tenant: &'a Account<'info, Tenant>,
+    user_account: &User,
+    visited_tenant: Option<&'a Account<'info, Tenant>>,
+    roaming_agreement: Option<&RoamingAgreement>,
+) -> Result<&'a Account<'info, Tenant>> {
+    if user_account.roaming_tenant == Pubkey::default() {
+        return Ok(tenant);
+    }
+
+    match (visited_tenant, roaming_agreement) {
+        (Some(visited_tenant), Some(roaming_agreement))
+            if visited_tenant.key() == user_account.roaming_tenant
+                && roaming_agreement.covers(&tenant.key(), &visited_tenant.key()) =>
+        {
+            Ok(visited_tenant)
+        }
+        _ => Err(error!(ProcessExitError::RoamingMismatch)),
+    }
+}
+
+/// Accounts a session's parking fee is paid from and split between.
+pub(crate) struct FeeAccounts<'a, 'info> {
+    /// Home tenant of the user
+    pub tenant: &'a Account<'info, Tenant>,
+    pub vault: &'a SystemAccount<'info>,
+    pub revenue: &'a SystemAccount<'info>,
+    pub platform_config: &'a PlatformConfig,
+    pub platform_treasury: &'a UncheckedAccount<'info>,
+    pub system_program: &'a Program<'info, System>,
+    pub fleet: Option<&'a Account<'info, Fleet>>,
+    pub fleet_vault: Option<&'a UncheckedAccount<'info>>,
+    pub fleet_member: Option<&'a mut Account<'info, FleetMember>>,
+    pub profile: Option<&'a mut Account<'info, Profile>>,
+    pub profile_vault: Option<&'a UncheckedAccount<'info>>,
+}
+
+impl<'a, 'info> FeeAccounts<'a, 'info> {
+    /// Pays the parking fee to the tenant revenue PDA and the platform, from the
+    /// fleet vault when the user parks on their employer's account, or from their
+    /// profile when it is linked, else from their own vault. Returns who paid, the
+    /// paying vault, and the tenant's and the platform's share.
+    pub fn pay(self, user_account: &mut User, amount: u64, now: i64) -> Result<(FeePayer, Pubkey, u64, u64)> {
+        match (self.fleet, self.fleet_vault, self.fleet_member) {
+            (Some(fleet), Some(fleet_vault), Some(fleet_member)) => {
+                let fleet_key = fleet.key();
+                let seeds: &[&[u8]] = &[b"fleet_vault", fleet_key.as_ref(), &[fleet.vault_bump]];
+                let expected_vault = Pubkey::create_program_address(seeds, &crate::ID)
+                    .map_err(|_| error!(ProcessExitError::FleetMismatch))?;
+
+                if fleet.tenant != self.tenant.key()
+                    || fleet_member.fleet != fleet_key
+                    || fleet_member.user != user_account.user
+                    || fleet_vault.key() != expected_vault
+                {
+                    return Err(error!(ProcessExitError::FleetMismatch));
+                }
+
+                fleet_member
+                    .charge(amount, now)
+                    .ok_or_else(|| error!(ProcessExitError::FleetSpendLimitExceeded))?;
+
+                // The fleet vault must stay rent exempt
+                let reserve = Rent::get()?.minimum_balance(0);
+                if amount > fleet_vault.lamports().saturating_sub(reserve) {
+                    return Err(error!(ProcessExitError::InsufficientVaultBalance));
+                }
+
+                let (tenant_amount, platform_amount) = collect_parking_fee(
+                    self.system_program,
+                    fleet_vault.to_account_info(),
+                    seeds,
+                    self.revenue.to_account_info(),
+                    self.platform_config,
+                    self.platform_treasury.to_account_info(),
+                    amount,
+                )?;
+                Ok((FeePayer::Fleet, fleet_vault.key(), tenant_amount, platform_amount))
+            }
+            (None, None, None) => match (self.profile, self.profile_vault) {
+                (Some(profile), Some(profile_vault)) => {
+                    let expected_vault =
+                        Pubkey::create_program_address(&profile.vault_seeds(), &crate::ID)
+                            .map_err(|_| error!(ProcessExitError::ProfileMismatch))?;
+
+                    if !self.tenant.accepts_profiles {
+                        return Err(error!(ProcessExitError::ProfilesNotAccepted));
+                    }
+                    if user_account.profile != profile.key() || profile_vault.key() != expected_vault {
+                        return Err(error!(ProcessExitError::ProfileMismatch));
+                    }
+
+                    // The profile vault must stay rent exempt
+                    let reserve = Rent::get()?.minimum_balance(0);
+                    if amount > profile_vault.lamports().saturating_sub(reserve) {
+                        return Err(error!(ProcessExitError::InsufficientVaultBalance));
+                    }
+
+                    let (tenant_amount, platform_amount) = collect_parking_fee(
+                        self.system_program,
+                        profile_vault.to_account_info(),
+                        &profile.vault_seeds(),
+                        self.revenue.to_account_info(),
+                        self.platform_config,
+                        self.platform_treasury.to_account_info(),
+                        amount,
+                    )?;
+                    profile.amount = profile_vault.lamports();
+                    Ok((FeePayer::Profile, profile_vault.key(), tenant_amount, platform_amount))
+                }
+                (None, None) => {
+                    user_account
+                        .debit_fee(amount)
+                        .ok_or_else(|| error!(ProcessExitError::InsufficientVaultBalance))?;
+                    if !can_pay_out(self.vault.lamports(), amount)? {
+                        return Err(error!(ProcessExitError::BelowRentReserve));
+                    }
+
+                    let (tenant_amount, platform_amount) = collect_parking_fee(
+                        self.system_program,
+                        self.vault.to_account_info(),
+                        &user_account.vault_seeds(),
+                        self.revenue.to_account_info(),
+                        self.platform_config,
+                        self.platform_treasury.to_account_info(),
+                        amount,
+                    )?;
+                    user_account.amount = self.vault.lamports();
+                    Ok((FeePayer::Vault, self.vault.key(), tenant_amount, platform_amount))
+                }
+                _ => Err(error!(ProcessExitError::ProfileMismatch)),
+            },
+            _ => Err(error!(ProcessExitError::FleetMismatch)),
+        }
+    }
+}
+
+/// Records what the home `tenant`, which collected a roaming session's fee, now owes
+/// the visited tenant.
+pub(crate) fn record_roaming_fee(
+    tenant: &mut Account<Tenant>,
+    user_account: &User,
+    roaming_agreement: Option<&mut RoamingAgreement>,
+    tenant_amount: u64,
+) -> Result<()> {
+    if user_account.roaming_tenant == Pubkey::default() {
+        return Ok(());
+    }
+
+    if let Some(roaming_agreement) = roaming_agreement {
+        let tenant_key = tenant.key();
+        let receivable = roaming_agreement
+            .record_visit(&tenant_key, tenant_amount)
+            .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
+        tenant.roaming_payable = tenant
+            .roaming_payable
+            .checked_add(receivable)
+            .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
+
+        emit!(RoamingFeeRecorded {
+            home_tenant: tenant_key,
+            visited_tenant: user_account.roaming_tenant,
+            user: user_account.user,
+            receivable,
+        });
+    }
+
+    Ok(())
+}
+
+/// Pays a parking fee out of a program-owned source (a user or fleet vault), split between
+/// the tenant and the platform. Returns the tenant's and the platform's share.
+pub(crate) fn collect_parking_fee<'info>(
+    system_program: &Program<'info, System>,
+    source: AccountInfo<'info>,
+    source_seeds: &[&[u8]],
+    tenant_treasury: AccountInfo<'info>,
+    platform_config: &PlatformConfig,
+    platform_treasury: AccountInfo<'info>,
+    amount: u64,
+) -> Result<(u64, u64)> {
+    if amount == 0 {
+        return Ok((0, 0));
+    }
+
+    let (tenant_amount, platform_amount) = platform_config
+        .split_fee(amount)
+        .ok_or_else(|| error!(ProcessExitError::AmountCalculationError))?;
+
+    // Ensure the source can cover the whole fee before moving anything
+    if amount > source.lamports() {
+        return Err(error!(ProcessExitError::InsufficientVaultBalance));
+    }
+
+    if tenant_amount > 0 {
+        transfer_from_pda(system_program, source.clone(), tenant_treasury, source_seeds, tenant_amount)?;
+    }
+    if platform_amount > 0 {
+        transfer_from_pda(system_program, source, platform_treasury, source_seeds, platform_amount)?;
+    }
+
+    Ok((tenant_amount, platform_amount))
+}
+
+/// Whether a system account holding `balance` can pay out `amount`: it must end up
+/// either empty or rent exempt, the only balances the runtime accepts.
+pub(crate) fn can_pay_out(balance: u64, amount: u64) -> Result<bool> {
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+
+    Ok(match balance.checked_sub(amount) {
+        Some(0) => true,
+        Some(remaining) => remaining >= rent_exempt_minimum,
+        None => false,
+    })
+}
+
+/// Moves `amount` lamports out of a system-owned PDA signed for by `seeds`.
+pub(crate) fn transfer_from_pda<'info>(
+    system_program: &Program<'info, System>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    let cpi_program = system_program.to_account_info();
+    let cpi_accounts = Transfer { from, to };
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+
+    transfer(cpi_ctx, amount)
+}
+
+#[event]
+pub struct ParkingFeeCollected {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    /// Vault the fee was paid from
+    pub source: Pubkey,
+    pub tenant_amount: u64,
+    pub platform_amount: u64,
+}
+
+#[event]
+pub struct RoamingFeeRecorded {
+    pub home_tenant: Pubkey,
+    pub visited_tenant: Pubkey,
+    pub user: Pubkey,
+    pub receivable: u64,
+}
+
 #[error_code]
 pub enum ProcessExitError {
     #[msg("Time calculation failed")]
@@ -126,4 +530,40 @@ pub enum ProcessExitError {
 
     #[msg("Vault does not have enough balance")]
     InsufficientVaultBalance,
-}
\ No newline at end of file
+
+    #[msg("Signer is neither the user nor a valid delegate")]
+    Unauthorized,
+
+    #[msg("Fee exceeds the delegate's remaining spend limit")]
+    DelegateSpendLimitExceeded,
+
+    #[msg("Fleet accounts are incomplete or do not belong to this user")]
+    FleetMismatch,
+
+    #[msg("Fee exceeds the member's remaining monthly fleet limit")]
+    FleetSpendLimitExceeded,
+
+    #[msg("Profile accounts are incomplete or not linked to this user")]
+    ProfileMismatch,
+
+    #[msg("Tenant does not accept profile payments")]
+    ProfilesNotAccepted,
+
+    #[msg("Roaming accounts are missing or do not match the session")]
+    RoamingMismatch,
+
+    #[msg("Fee would leave the vault below its rent-exempt minimum")]
+    BelowRentReserve,
+
+    #[msg("Gate ticket is missing, unsigned by a registered gate or not valid for this session")]
+    InvalidGateTicket,
+
+    #[msg("Explicit timestamps must be co-signed by the tenant's operator")]
+    OperatorSignatureRequired,
+
+    #[msg("Timestamp is in the future or outside the tenant's backdating window")]
+    BackdateOutOfWindow,
+
+    #[msg("A gate ticket and an explicit timestamp cannot be used together")]
+    ConflictingTimestamps,
+}
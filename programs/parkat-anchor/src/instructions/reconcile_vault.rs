@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User};
+
+#[derive(Accounts)]
+pub struct ReconcileVault<'info> {
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    /// CHECK: Wallet of the vault owner, only used to derive the user and vault PDAs
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> ReconcileVault<'info> {
+    /// Credits lamports sent straight to the vault, outside `deposit` and
+    /// `deposit_for`, to the user's ledger. Permissionless.
+    pub fn reconcile_vault(&mut self) -> Result<()> {
+        let vault_balance = self.vault.lamports();
+        let user_account = &mut self.user_account;
+
+        if user_account.available_balance > vault_balance {
+            return Err(error!(ReconcileVaultError::LedgerExceedsVault));
+        }
+
+        // The rent-exempt reserve funded at registration is not the user's to spend
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let untracked = vault_balance
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(user_account.available_balance);
+
+        if untracked > 0 {
+            user_account
+                .credit_deposit(untracked)
+                .ok_or_else(|| error!(ReconcileVaultError::AmountCalculationError))?;
+        }
+        user_account.amount = vault_balance;
+
+        emit!(VaultReconciled {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            credited: untracked,
+            available_balance: user_account.available_balance,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct VaultReconciled {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub credited: u64,
+    pub available_balance: u64,
+}
+
+#[error_code]
+pub enum ReconcileVaultError {
+    #[msg("Tracked balance exceeds the vault's lamports")]
+    LedgerExceedsVault,
+
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::state::{Tenant, User};
+use crate::state::{
+    GateDirection, GateKey, GateTicket, PlatformConfig, RoamingAgreement, Tenant, User, UserStatus,
+};
 
 #[derive(Accounts)]
 pub struct RecordParkingStart<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// Owner of the user account or its delegate
+    pub authority: Signer<'info>,
 
     #[account(
         mut,
@@ -19,31 +21,208 @@ pub struct RecordParkingStart<'info> {
 
     #[account(
         mut,
-        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        seeds = [b"user", tenant.key().as_ref(), user_account.user.as_ref()],
         bump = user_account.state_bump
     )]
     pub user_account: Account<'info, User>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    /// Tenant whose lot the user is entering when roaming away from `tenant`
+    pub visited_tenant: Option<Account<'info, Tenant>>,
+
+    /// Agreement between `tenant` and `visited_tenant`
+    pub roaming_agreement: Option<Account<'info, RoamingAgreement>>,
+
+    /// Gate device that signed an entry ticket, registered with the lot's tenant
+    pub gate_key: Option<Account<'info, GateKey>>,
+
+    /// CHECK: Instructions sysvar holding the Ed25519 verification of the ticket
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Tenant operator co-signing an explicit start time
+    pub operator: Option<Signer<'info>>,
 }
 
 impl<'info> RecordParkingStart<'info> {
-    pub fn record_parking_start(&mut self) -> Result<()> {
+    pub fn record_parking_start(&mut self, timestamp: Option<i64>) -> Result<()> {
+        if self.platform_config.paused || self.tenant.paused {
+            return Err(error!(RecordParkingStartError::Paused));
+        }
+
+        let clock = Clock::get()?;
+        let current_time = clock.unix_timestamp;
         let user_account = &mut self.user_account;
 
+        if !user_account.is_authorized(&self.authority.key(), current_time) {
+            return Err(error!(RecordParkingStartError::Unauthorized));
+        }
+
+        match user_account.status {
+            UserStatus::Active => {}
+            UserStatus::PendingApproval => return Err(error!(RecordParkingStartError::NotApproved)),
+            UserStatus::Suspended => return Err(error!(RecordParkingStartError::Suspended)),
+        }
+
         // Check if user is already parked
         if user_account.is_parked {
-            return Err(error!(Error::AlreadyParked));
+            return Err(error!(RecordParkingStartError::AlreadyParked));
         }
 
-        // Update parking start time and status
-        user_account.time_stamp = Clock::get()?.unix_timestamp;
+        // Roaming sessions need an agreement between the home and visited tenants
+        user_account.roaming_tenant = match (&self.visited_tenant, &self.roaming_agreement) {
+            (Some(visited_tenant), Some(roaming_agreement)) => {
+                if !roaming_agreement.covers(&self.tenant.key(), &visited_tenant.key()) {
+                    return Err(error!(RecordParkingStartError::NoRoamingAgreement));
+                }
+                if visited_tenant.paused {
+                    return Err(error!(RecordParkingStartError::Paused));
+                }
+                visited_tenant.key()
+            }
+            (None, None) => Pubkey::default(),
+            _ => return Err(error!(RecordParkingStartError::NoRoamingAgreement)),
+        };
+
+        // The session is timed against the tenant whose lot the car is in
+        let lot_tenant = self.visited_tenant.as_ref().unwrap_or(&self.tenant);
+
+        // A signed gate ticket starts the session when the car actually entered,
+        // which lets gates that were offline catch up later
+        let ticket_time = match (&self.gate_key, &self.instructions_sysvar) {
+            (Some(gate_key), Some(instructions_sysvar)) => {
+                // Tickets from before the last exit would restart an old session
+                let ticket = GateTicket::load(instructions_sysvar, &gate_key.key)?
+                    .filter(|ticket| {
+                        gate_key.tenant == lot_tenant.key()
+                            && ticket.is_valid_for(
+                                &lot_tenant.key(),
+                                &user_account.number_plate,
+                                GateDirection::Entry,
+                                current_time,
+                            )
+                            && ticket.timestamp >= user_account.last_session.end_time
+                    })
+                    .ok_or_else(|| error!(RecordParkingStartError::InvalidGateTicket))?;
+
+                emit!(GateTicketAccepted {
+                    tenant: lot_tenant.key(),
+                    user: user_account.user,
+                    gate: gate_key.key,
+                    direction: ticket.direction,
+                    timestamp: ticket.timestamp,
+                });
+
+                Some(ticket.timestamp)
+            }
+            (None, None) => None,
+            _ => return Err(error!(RecordParkingStartError::InvalidGateTicket)),
+        };
+
+        // An explicit start time is only honoured when the tenant's operator co-signs
+        let backdated_time = match timestamp {
+            Some(timestamp) => {
+                let operator = self
+                    .operator
+                    .as_ref()
+                    .filter(|operator| lot_tenant.is_operator(&operator.key()))
+                    .ok_or_else(|| error!(RecordParkingStartError::OperatorSignatureRequired))?;
+
+                // Starting before the last exit would overlap the previous session
+                if !lot_tenant.is_within_backdate_window(timestamp, current_time)
+                    || timestamp < user_account.last_session.end_time
+                {
+                    return Err(error!(RecordParkingStartError::BackdateOutOfWindow));
+                }
+
+                emit!(SessionBackdated {
+                    tenant: lot_tenant.key(),
+                    user: user_account.user,
+                    operator: operator.key(),
+                    direction: GateDirection::Entry,
+                    timestamp,
+                    recorded_at: current_time,
+                });
+
+                Some(timestamp)
+            }
+            None => None,
+        };
+
+        // Update parking start time and status. The slot of a past entry is unknown,
+        // so such sessions run on wall-clock time.
+        match (ticket_time, backdated_time) {
+            (Some(_), Some(_)) => return Err(error!(RecordParkingStartError::ConflictingTimestamps)),
+            (Some(start_time), None) | (None, Some(start_time)) => {
+                user_account.time_stamp = start_time;
+                user_account.start_slot = 0;
+            }
+            (None, None) => {
+                user_account.time_stamp = current_time;
+                user_account.start_slot = clock.slot;
+            }
+        }
         user_account.is_parked = true;
 
         Ok(())
     }
 }
 
+#[event]
+pub struct GateTicketAccepted {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub gate: Pubkey,
+    pub direction: GateDirection,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SessionBackdated {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub operator: Pubkey,
+    pub direction: GateDirection,
+    /// Time the session was started or ended at
+    pub timestamp: i64,
+    /// On-chain time the backdated event was submitted
+    pub recorded_at: i64,
+}
+
 #[error_code]
-pub enum Error {
+pub enum RecordParkingStartError {
     #[msg("User is already parked")]
     AlreadyParked,
+
+    #[msg("Parking is paused for this tenant")]
+    Paused,
+
+    #[msg("User has not been approved by the tenant")]
+    NotApproved,
+
+    #[msg("User has been suspended by the tenant")]
+    Suspended,
+
+    #[msg("Signer is neither the user nor a valid delegate")]
+    Unauthorized,
+
+    #[msg("No roaming agreement between the home and visited tenants")]
+    NoRoamingAgreement,
+
+    #[msg("Gate ticket is missing, unsigned by a registered gate or not valid for this session")]
+    InvalidGateTicket,
+
+    #[msg("Explicit timestamps must be co-signed by the tenant's operator")]
+    OperatorSignatureRequired,
+
+    #[msg("Timestamp is in the future or outside the tenant's backdating window")]
+    BackdateOutOfWindow,
+
+    #[msg("A gate ticket and an explicit timestamp cannot be used together")]
+    ConflictingTimestamps,
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Fleet, FleetMember};
+
+#[derive(Accounts)]
+pub struct RemoveFleetMember<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fleet", fleet.tenant.as_ref(), admin.key().as_ref()],
+        bump = fleet.bump,
+    )]
+    pub fleet: Account<'info, Fleet>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"fleet_member", fleet.key().as_ref(), fleet_member.user.as_ref()],
+        bump = fleet_member.bump,
+    )]
+    pub fleet_member: Account<'info, FleetMember>,
+}
+
+impl<'info> RemoveFleetMember<'info> {
+    pub fn remove_fleet_member(&mut self) -> Result<()> {
+        self.fleet.member_count = self.fleet.member_count.saturating_sub(1);
+
+        emit!(FleetMemberRemoved {
+            fleet: self.fleet.key(),
+            user: self.fleet_member.user,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct FleetMemberRemoved {
+    pub fleet: Pubkey,
+    pub user: Pubkey,
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{GateKey, Tenant};
+
+#[derive(Accounts)]
+pub struct RemoveGateKey<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"gate_key", tenant.key().as_ref(), gate_key.key.as_ref()],
+        bump = gate_key.bump,
+    )]
+    pub gate_key: Account<'info, GateKey>,
+}
+
+impl<'info> RemoveGateKey<'info> {
+    pub fn remove_gate_key(&mut self) -> Result<()> {
+        emit!(GateKeyRemoved {
+            tenant: self.tenant.key(),
+            key: self.gate_key.key,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct GateKeyRemoved {
+    pub tenant: Pubkey,
+    pub key: Pubkey,
+}
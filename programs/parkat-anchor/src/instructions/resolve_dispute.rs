@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::{Dispute, DisputeStatus, FeePayer, Fleet, FleetMember, Profile, Tenant, User};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// Tenant treasury that collected the disputed fee
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump = tenant.revenue_bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    /// CHECK: Wallet of the disputing user, only used to derive the user and vault PDAs
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"dispute",
+            user_account.key().as_ref(),
+            dispute.session_id.to_le_bytes().as_ref(),
+        ],
+        bump = dispute.bump,
+        has_one = tenant,
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Fleet that paid the disputed fee
+    pub fleet: Option<Account<'info, Fleet>>,
+
+    /// CHECK: Fleet vault, verified against the fleet's stored bump
+    #[account(mut)]
+    pub fleet_vault: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub fleet_member: Option<Account<'info, FleetMember>>,
+
+    /// Profile that paid the disputed fee
+    #[account(mut)]
+    pub profile: Option<Account<'info, Profile>>,
+
+    /// CHECK: Profile vault, verified against the profile's stored bump
+    #[account(mut)]
+    pub profile_vault: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> ResolveDispute<'info> {
+    /// Closes the dispute, refunding up to the tenant's share of the disputed fee
+    /// to whoever paid it: the user's vault, their fleet or their profile.
+    pub fn resolve_dispute(&mut self, refund: u64) -> Result<()> {
+        let dispute = &mut self.dispute;
+
+        if dispute.status != DisputeStatus::Open {
+            return Err(error!(ResolveDisputeError::DisputeNotOpen));
+        }
+
+        if refund > dispute.fee {
+            return Err(error!(ResolveDisputeError::RefundExceedsFee));
+        }
+
+        // The platform's cut never reached the tenant, so it can't be refunded from revenue
+        if refund > dispute.tenant_amount {
+            return Err(error!(ResolveDisputeError::RefundExceedsTenantShare));
+        }
+
+        if refund > 0 {
+            // Refund out of the revenue PDA without touching its rent-exempt reserve
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+            let available = self.revenue.lamports().saturating_sub(rent_exempt_minimum);
+            if refund > available {
+                return Err(error!(ResolveDisputeError::InsufficientRevenue));
+            }
+
+            let tenant_key = self.tenant.key();
+            let revenue_seeds: &[&[u8]] = &[
+                b"revenue",
+                tenant_key.as_ref(),
+                &[self.tenant.revenue_bump],
+            ];
+
+            match dispute.payer {
+                FeePayer::Vault => {
+                    transfer_from_pda(
+                        &self.system_program,
+                        self.revenue.to_account_info(),
+                        self.vault.to_account_info(),
+                        revenue_seeds,
+                        refund,
+                    )?;
+
+                    self.user_account.amount = self.vault.lamports();
+                    self.user_account
+                        .credit_refund(refund)
+                        .ok_or_else(|| error!(ResolveDisputeError::AmountCalculationError))?;
+                }
+                FeePayer::Fleet => match (&self.fleet, &self.fleet_vault, &mut self.fleet_member) {
+                    (Some(fleet), Some(fleet_vault), Some(fleet_member)) => {
+                        let fleet_key = fleet.key();
+                        let expected_vault = Pubkey::create_program_address(
+                            &[b"fleet_vault", fleet_key.as_ref(), &[fleet.vault_bump]],
+                            &crate::ID,
+                        )
+                        .map_err(|_| error!(ResolveDisputeError::PayerMismatch))?;
+
+                        if fleet.tenant != tenant_key
+                            || fleet_member.fleet != fleet_key
+                            || fleet_member.user != self.user_account.user
+                            || fleet_vault.key() != expected_vault
+                        {
+                            return Err(error!(ResolveDisputeError::PayerMismatch));
+                        }
+
+                        transfer_from_pda(
+                            &self.system_program,
+                            self.revenue.to_account_info(),
+                            fleet_vault.to_account_info(),
+                            revenue_seeds,
+                            refund,
+                        )?;
+
+                        // The refunded fee no longer counts against the member's limit
+                        fleet_member.spent_this_period =
+                            fleet_member.spent_this_period.saturating_sub(refund);
+                    }
+                    _ => return Err(error!(ResolveDisputeError::PayerMismatch)),
+                },
+                FeePayer::Profile => match (&mut self.profile, &self.profile_vault) {
+                    (Some(profile), Some(profile_vault)) => {
+                        let expected_vault =
+                            Pubkey::create_program_address(&profile.vault_seeds(), &crate::ID)
+                                .map_err(|_| error!(ResolveDisputeError::PayerMismatch))?;
+
+                        if profile.owner != self.user_account.user
+                            || profile_vault.key() != expected_vault
+                        {
+                            return Err(error!(ResolveDisputeError::PayerMismatch));
+                        }
+
+                        transfer_from_pda(
+                            &self.system_program,
+                            self.revenue.to_account_info(),
+                            profile_vault.to_account_info(),
+                            revenue_seeds,
+                            refund,
+                        )?;
+
+                        profile.amount = profile_vault.lamports();
+                    }
+                    _ => return Err(error!(ResolveDisputeError::PayerMismatch)),
+                },
+            }
+
+            self.tenant.total_collected = self.tenant.total_collected.saturating_sub(refund);
+        }
+
+        // Whatever was not refunded is free to be paid out again
+        self.tenant.disputed_amount =
+            self.tenant.disputed_amount.saturating_sub(dispute.tenant_amount);
+
+        dispute.refunded = refund;
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at = Clock::get()?.unix_timestamp;
+
+        emit!(DisputeResolved {
+            tenant: dispute.tenant,
+            user: dispute.user,
+            session_id: dispute.session_id,
+            payer: dispute.payer,
+            refunded: refund,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub session_id: u64,
+    pub payer: FeePayer,
+    pub refunded: u64,
+}
+
+#[error_code]
+pub enum ResolveDisputeError {
+    #[msg("Dispute has already been resolved")]
+    DisputeNotOpen,
+
+    #[msg("Refund cannot exceed the disputed fee")]
+    RefundExceedsFee,
+
+    #[msg("Tenant revenue does not cover the refund")]
+    InsufficientRevenue,
+
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+
+    #[msg("Refund cannot exceed the tenant's share of the disputed fee")]
+    RefundExceedsTenantShare,
+
+    #[msg("Refund accounts do not match the account that paid the fee")]
+    PayerMismatch,
+}
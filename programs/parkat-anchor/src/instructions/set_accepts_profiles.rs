@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Tenant;
+
+#[derive(Accounts)]
+pub struct SetAcceptsProfiles<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetAcceptsProfiles<'info> {
+    pub fn set_accepts_profiles(&mut self, accepts_profiles: bool) -> Result<()> {
+        self.tenant.accepts_profiles = accepts_profiles;
+
+        emit!(TenantProfilesChanged {
+            tenant: self.tenant.key(),
+            accepts_profiles,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TenantProfilesChanged {
+    pub tenant: Pubkey,
+    pub accepts_profiles: bool,
+}
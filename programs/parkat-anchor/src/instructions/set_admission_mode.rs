@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{AdmissionMode, Tenant};
+
+#[derive(Accounts)]
+pub struct SetAdmissionMode<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetAdmissionMode<'info> {
+    pub fn set_admission_mode(
+        &mut self,
+        admission_mode: AdmissionMode,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        if admission_mode == AdmissionMode::Allowlist && allowlist_root == [0u8; 32] {
+            return Err(error!(SetAdmissionModeError::MissingAllowlistRoot));
+        }
+
+        let tenant = &mut self.tenant;
+        tenant.admission_mode = admission_mode;
+        tenant.allowlist_root = allowlist_root;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum SetAdmissionModeError {
+    #[msg("Allowlist mode requires a Merkle root")]
+    MissingAllowlistRoot,
+}
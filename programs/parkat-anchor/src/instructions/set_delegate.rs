@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User};
+
+#[derive(Accounts)]
+pub struct SetDelegate<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> SetDelegate<'info> {
+    /// Registers a session key for gate devices. Passing `Pubkey::default()` revokes it.
+    pub fn set_delegate(&mut self, delegate: Pubkey, expires_at: i64, spend_limit: u64) -> Result<()> {
+        if delegate != Pubkey::default() && expires_at <= Clock::get()?.unix_timestamp {
+            return Err(error!(SetDelegateError::InvalidExpiry));
+        }
+
+        let user_account = &mut self.user_account;
+        user_account.delegate = delegate;
+        user_account.delegate_expires_at = expires_at;
+        user_account.delegate_spend_limit = spend_limit;
+        user_account.delegate_spent = 0;
+
+        emit!(DelegateSet {
+            tenant: self.tenant.key(),
+            user: user_account.user,
+            delegate,
+            expires_at,
+            spend_limit,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct DelegateSet {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub delegate: Pubkey,
+    pub expires_at: i64,
+    pub spend_limit: u64,
+}
+
+#[error_code]
+pub enum SetDelegateError {
+    #[msg("Delegate expiry must be in the future")]
+    InvalidExpiry,
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Fleet, FleetMember, User};
+
+#[derive(Accounts)]
+pub struct SetFleetMember<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fleet", fleet.tenant.as_ref(), admin.key().as_ref()],
+        bump = fleet.bump,
+    )]
+    pub fleet: Account<'info, Fleet>,
+
+    /// CHECK: Wallet of the employee, only used to derive the user and member PDAs
+    pub user: UncheckedAccount<'info>,
+
+    /// Members must be registered with the fleet's tenant
+    #[account(
+        seeds = [b"user", fleet.tenant.as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FleetMember::INIT_SPACE,
+        seeds = [b"fleet_member", fleet.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub fleet_member: Account<'info, FleetMember>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetFleetMember<'info> {
+    /// Adds a member to the fleet, or updates the monthly limit of an existing one.
+    pub fn set_fleet_member(&mut self, bumps: &SetFleetMemberBumps, monthly_limit: u64) -> Result<()> {
+        let fleet_member = &mut self.fleet_member;
+
+        if fleet_member.fleet == Pubkey::default() {
+            fleet_member.fleet = self.fleet.key();
+            fleet_member.user = self.user.key();
+            fleet_member.period_start = Clock::get()?.unix_timestamp;
+            fleet_member.bump = bumps.fleet_member;
+
+            self.fleet.member_count = self
+                .fleet
+                .member_count
+                .checked_add(1)
+                .ok_or_else(|| error!(SetFleetMemberError::TooManyMembers))?;
+        }
+        fleet_member.monthly_limit = monthly_limit;
+
+        emit!(FleetMemberSet {
+            fleet: self.fleet.key(),
+            user: self.user.key(),
+            monthly_limit,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct FleetMemberSet {
+    pub fleet: Pubkey,
+    pub user: Pubkey,
+    pub monthly_limit: u64,
+}
+
+#[error_code]
+pub enum SetFleetMemberError {
+    #[msg("Fleet has too many members")]
+    TooManyMembers,
+}
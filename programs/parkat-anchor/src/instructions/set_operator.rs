@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, MAX_BACKDATE_SECONDS};
+
+#[derive(Accounts)]
+pub struct SetOperator<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetOperator<'info> {
+    /// Sets the key allowed to co-sign backdated session times and how far back
+    /// it may date them. The default pubkey disables backdating.
+    pub fn set_operator(&mut self, operator: Pubkey, max_backdate_seconds: u32) -> Result<()> {
+        if max_backdate_seconds > MAX_BACKDATE_SECONDS {
+            return Err(error!(SetOperatorError::BackdateWindowTooLarge));
+        }
+
+        self.tenant.operator = operator;
+        self.tenant.max_backdate_seconds = max_backdate_seconds;
+
+        emit!(OperatorChanged {
+            tenant: self.tenant.key(),
+            operator,
+            max_backdate_seconds,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct OperatorChanged {
+    pub tenant: Pubkey,
+    pub operator: Pubkey,
+    pub max_backdate_seconds: u32,
+}
+
+#[error_code]
+pub enum SetOperatorError {
+    #[msg("Backdating window exceeds the allowed maximum")]
+    BackdateWindowTooLarge,
+}
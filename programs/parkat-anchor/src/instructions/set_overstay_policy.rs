@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{OverstayPenalty, Tenant, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct SetOverstayPolicy<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetOverstayPolicy<'info> {
+    pub fn set_overstay_policy(
+        &mut self,
+        max_duration_seconds: u64,
+        penalty: OverstayPenalty,
+    ) -> Result<()> {
+        // A penalty multiplier must never reduce the fee
+        if let OverstayPenalty::Multiplier { multiplier_bps } = penalty {
+            if u64::from(multiplier_bps) < BPS_DENOMINATOR {
+                return Err(error!(SetOverstayPolicyError::InvalidPenaltyMultiplier));
+            }
+        }
+
+        let tenant = &mut self.tenant;
+        tenant.max_duration_seconds = max_duration_seconds;
+        tenant.overstay_penalty = penalty;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum SetOverstayPolicyError {
+    #[msg("Overstay multiplier must be at least 10000 basis points")]
+    InvalidPenaltyMultiplier,
+}
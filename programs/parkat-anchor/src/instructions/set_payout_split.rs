@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{PayoutRecipient, PayoutSplit, Tenant, BPS_DENOMINATOR, MAX_PAYOUT_RECIPIENTS};
+
+#[derive(Accounts)]
+pub struct SetPayoutSplit<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        mut,
+        seeds = [b"payout_split", tenant.key().as_ref()],
+        bump = payout_split.bump,
+        has_one = tenant,
+    )]
+    pub payout_split: Account<'info, PayoutSplit>,
+}
+
+impl<'info> SetPayoutSplit<'info> {
+    /// Replaces the recipients of a split created by `init_payout_split`.
+    pub fn set_payout_split(&mut self, recipients: Vec<PayoutRecipient>) -> Result<()> {
+        check_recipients(&recipients)?;
+
+        self.payout_split.recipients = recipients;
+
+        Ok(())
+    }
+}
+
+/// Checks the recipient count and that the shares add up to exactly 100%.
+pub(crate) fn check_recipients(recipients: &[PayoutRecipient]) -> Result<()> {
+    if recipients.is_empty() || recipients.len() > MAX_PAYOUT_RECIPIENTS {
+        return Err(error!(SetPayoutSplitError::InvalidRecipientCount));
+    }
+
+    let total_bps = recipients
+        .iter()
+        .try_fold(0u64, |total, recipient| total.checked_add(u64::from(recipient.share_bps)))
+        .ok_or_else(|| error!(SetPayoutSplitError::InvalidShares))?;
+    if total_bps != BPS_DENOMINATOR {
+        return Err(error!(SetPayoutSplitError::InvalidShares));
+    }
+
+    Ok(())
+}
+
+#[error_code]
+pub enum SetPayoutSplitError {
+    #[msg("Payout split must have between 1 and 5 recipients")]
+    InvalidRecipientCount,
+
+    #[msg("Recipient shares must add up to 10000 basis points")]
+    InvalidShares,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::PlatformConfig;
+
+#[derive(Accounts)]
+pub struct SetPlatformPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+        has_one = authority,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+}
+
+impl<'info> SetPlatformPaused<'info> {
+    pub fn set_platform_paused(&mut self, paused: bool) -> Result<()> {
+        self.platform_config.paused = paused;
+
+        emit!(PlatformPauseChanged { paused });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct PlatformPauseChanged {
+    pub paused: bool,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{RoundingMode, Tenant};
+
+#[derive(Accounts)]
+pub struct SetRoundingMode<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetRoundingMode<'info> {
+    pub fn set_rounding_mode(&mut self, rounding_mode: RoundingMode) -> Result<()> {
+        self.tenant.rounding_mode = rounding_mode;
+
+        emit!(RoundingModeChanged {
+            tenant: self.tenant.key(),
+            rounding_mode,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RoundingModeChanged {
+    pub tenant: Pubkey,
+    pub rounding_mode: RoundingMode,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Tenant;
+
+#[derive(Accounts)]
+pub struct SetTenantPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetTenantPaused<'info> {
+    pub fn set_tenant_paused(&mut self, paused: bool) -> Result<()> {
+        self.tenant.paused = paused;
+
+        emit!(TenantPauseChanged {
+            tenant: self.tenant.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TenantPauseChanged {
+    pub tenant: Pubkey,
+    pub paused: bool,
+}
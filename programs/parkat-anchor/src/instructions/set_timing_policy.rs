@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, TimingMode, MAX_CLOCK_SKEW_TOLERANCE_SECONDS};
+
+#[derive(Accounts)]
+pub struct SetTimingPolicy<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+}
+
+impl<'info> SetTimingPolicy<'info> {
+    pub fn set_timing_policy(
+        &mut self,
+        timing_mode: TimingMode,
+        clock_skew_tolerance_seconds: u32,
+    ) -> Result<()> {
+        if clock_skew_tolerance_seconds > MAX_CLOCK_SKEW_TOLERANCE_SECONDS {
+            return Err(error!(SetTimingPolicyError::ToleranceTooLarge));
+        }
+
+        self.tenant.timing_mode = timing_mode;
+        self.tenant.clock_skew_tolerance_seconds = clock_skew_tolerance_seconds;
+
+        emit!(TimingPolicyChanged {
+            tenant: self.tenant.key(),
+            timing_mode,
+            clock_skew_tolerance_seconds,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TimingPolicyChanged {
+    pub tenant: Pubkey,
+    pub timing_mode: TimingMode,
+    pub clock_skew_tolerance_seconds: u32,
+}
+
+#[error_code]
+pub enum SetTimingPolicyError {
+    #[msg("Clock-skew tolerance exceeds the allowed maximum")]
+    ToleranceTooLarge,
+}
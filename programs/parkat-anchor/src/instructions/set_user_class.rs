@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User, UserClass, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct SetUserClass<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Wallet of the user whose class is being set, only used to derive the user PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> SetUserClass<'info> {
+    pub fn set_user_class(&mut self, class: UserClass) -> Result<()> {
+        // A discount can never charge more than the standard fee
+        if let UserClass::Discounted { multiplier_bps } = class {
+            if u64::from(multiplier_bps) > BPS_DENOMINATOR {
+                return Err(error!(SetUserClassError::InvalidMultiplier));
+            }
+        }
+
+        self.user_account.class = class;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum SetUserClassError {
+    #[msg("Discount multiplier cannot exceed 10000 basis points")]
+    InvalidMultiplier,
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::{RoamingAgreement, Tenant};
+
+#[derive(Accounts)]
+pub struct SettleRoaming<'info> {
+    #[account(
+        mut,
+        seeds = [b"tenant", tenant_a.admin.as_ref()],
+        bump = tenant_a.bump,
+    )]
+    pub tenant_a: Account<'info, Tenant>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", tenant_b.admin.as_ref()],
+        bump = tenant_b.bump,
+    )]
+    pub tenant_b: Account<'info, Tenant>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant_a.key().as_ref()],
+        bump = tenant_a.revenue_bump,
+    )]
+    pub revenue_a: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant_b.key().as_ref()],
+        bump = tenant_b.revenue_bump,
+    )]
+    pub revenue_b: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"roaming", tenant_a.key().as_ref(), tenant_b.key().as_ref()],
+        bump = roaming_agreement.bump,
+    )]
+    pub roaming_agreement: Account<'info, RoamingAgreement>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleRoaming<'info> {
+    /// Nets the receivables and pays the balance from the debtor's revenue PDA.
+    /// Permissionless; whatever the debtor cannot cover yet stays owed.
+    pub fn settle_roaming(&mut self) -> Result<()> {
+        let agreement = &mut self.roaming_agreement;
+        let offset = agreement.net();
+
+        let a_pays = agreement.a_owes_b > 0;
+        let (debtor, debtor_revenue, creditor_revenue, owed) = if a_pays {
+            (&self.tenant_a, &self.revenue_a, &self.revenue_b, agreement.a_owes_b)
+        } else {
+            (&self.tenant_b, &self.revenue_b, &self.revenue_a, agreement.b_owes_a)
+        };
+
+        // The debtor's revenue PDA keeps its rent-exempt reserve
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let available = debtor_revenue.lamports().saturating_sub(rent_exempt_minimum);
+        let paid = owed.min(available);
+
+        if paid > 0 {
+            let debtor_key = debtor.key();
+            let seeds: &[&[u8]] = &[b"revenue", debtor_key.as_ref(), &[debtor.revenue_bump]];
+
+            transfer_from_pda(
+                &self.system_program,
+                debtor_revenue.to_account_info(),
+                creditor_revenue.to_account_info(),
+                seeds,
+                paid,
+            )?;
+        }
+
+        // Netted receivables no longer have to be held back by either tenant
+        self.tenant_a.roaming_payable = self.tenant_a.roaming_payable.saturating_sub(offset);
+        self.tenant_b.roaming_payable = self.tenant_b.roaming_payable.saturating_sub(offset);
+
+        // Move the settled amount between the tenants' collected totals
+        let (debtor, creditor) = if a_pays {
+            (&mut self.tenant_a, &mut self.tenant_b)
+        } else {
+            (&mut self.tenant_b, &mut self.tenant_a)
+        };
+        debtor.total_collected = debtor.total_collected.saturating_sub(paid);
+        debtor.roaming_payable = debtor.roaming_payable.saturating_sub(paid);
+        creditor.total_collected = creditor
+            .total_collected
+            .checked_add(paid)
+            .ok_or_else(|| error!(SettleRoamingError::AmountCalculationError))?;
+
+        if a_pays {
+            agreement.a_owes_b -= paid;
+        } else {
+            agreement.b_owes_a -= paid;
+        }
+        agreement.last_settled_at = Clock::get()?.unix_timestamp;
+
+        emit!(RoamingSettled {
+            debtor: debtor.key(),
+            creditor: creditor.key(),
+            paid,
+            outstanding: owed - paid,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RoamingSettled {
+    pub debtor: Pubkey,
+    pub creditor: Pubkey,
+    pub paid: u64,
+    pub outstanding: u64,
+}
+
+#[error_code]
+pub enum SettleRoamingError {
+    #[msg("Amount calculation failed")]
+    AmountCalculationError,
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Tenant, User, UserStatus};
+
+#[derive(Accounts)]
+pub struct SuspendUser<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Wallet of the user being suspended, only used to derive the user PDA
+    pub user: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = user_account.state_bump,
+    )]
+    pub user_account: Account<'info, User>,
+}
+
+impl<'info> SuspendUser<'info> {
+    /// Only active users can be suspended, so lifting the bar always restores
+    /// `Active` and never skips a pending approval.
+    pub fn suspend_user(&mut self) -> Result<()> {
+        match self.user_account.status {
+            UserStatus::Active => {}
+            UserStatus::Suspended => return Err(error!(SuspendUserError::AlreadySuspended)),
+            UserStatus::PendingApproval => return Err(error!(SuspendUserError::NotActive)),
+        }
+
+        self.user_account.status = UserStatus::Suspended;
+
+        emit!(UserSuspensionChanged {
+            tenant: self.tenant.key(),
+            user: self.user_account.user,
+            suspended: true,
+        });
+
+        Ok(())
+    }
+
+    pub fn unsuspend_user(&mut self) -> Result<()> {
+        if self.user_account.status != UserStatus::Suspended {
+            return Err(error!(SuspendUserError::NotSuspended));
+        }
+
+        self.user_account.status = UserStatus::Active;
+
+        emit!(UserSuspensionChanged {
+            tenant: self.tenant.key(),
+            user: self.user_account.user,
+            suspended: false,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct UserSuspensionChanged {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub suspended: bool,
+}
+
+#[error_code]
+pub enum SuspendUserError {
+    #[msg("User is already suspended")]
+    AlreadySuspended,
+
+    #[msg("User is not suspended")]
+    NotSuspended,
+
+    #[msg("Only active users can be suspended")]
+    NotActive,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Receipt;
+
+#[derive(Accounts)]
+pub struct VerifyReceipt<'info> {
+    #[account(
+        seeds = [
+            b"receipt",
+            receipt.user_account.as_ref(),
+            receipt.receipt_number.to_le_bytes().as_ref(),
+        ],
+        bump = receipt.bump,
+    )]
+    pub receipt: Account<'info, Receipt>,
+}
+
+impl<'info> VerifyReceipt<'info> {
+    /// Read-only check for auditors: whether `commitment` matches a receipt issued by the program.
+    pub fn verify_receipt(&self, commitment: [u8; 32]) -> Result<bool> {
+        let receipt = &self.receipt;
+
+        Ok(receipt.commitment == commitment && receipt.compute_commitment() == commitment)
+    }
+}
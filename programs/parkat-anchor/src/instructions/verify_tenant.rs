@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::helpers::grow_account;
+use crate::state::{PlatformConfig, Tenant, TenantRegistry};
+
+#[derive(Accounts)]
+pub struct VerifyTenant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"platform"],
+        bump = platform_config.bump,
+        has_one = authority,
+    )]
+    pub platform_config: Account<'info, PlatformConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = tenant_registry.bump,
+    )]
+    pub tenant_registry: Account<'info, TenantRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VerifyTenant<'info> {
+    pub fn verify_tenant(&mut self) -> Result<()> {
+        if self.tenant.verified {
+            return Err(error!(VerifyTenantError::AlreadyVerified));
+        }
+
+        let tenant_count = self.tenant_registry.tenants.len();
+        if tenant_count >= TenantRegistry::MAX_TENANTS {
+            return Err(error!(VerifyTenantError::RegistryFull));
+        }
+
+        grow_account(
+            &self.tenant_registry.to_account_info(),
+            &self.authority,
+            &self.system_program,
+            TenantRegistry::space(tenant_count + 1),
+        )?;
+
+        self.tenant.verified = true;
+        self.tenant_registry.tenants.push(self.tenant.key());
+
+        emit!(TenantVerified {
+            tenant: self.tenant.key(),
+            admin: self.tenant.admin,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct TenantVerified {
+    pub tenant: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[error_code]
+pub enum VerifyTenantError {
+    #[msg("Tenant is already verified")]
+    AlreadyVerified,
+    #[msg("Tenant registry is full")]
+    RegistryFull,
+}
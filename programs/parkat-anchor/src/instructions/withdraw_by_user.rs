@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::{can_pay_out, transfer_from_pda};
+use crate::state::{Tenant, User};
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", tenant_admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    /// CHECK: Tenant admin - must match the admin used during tenant initialization
+    pub tenant_admin: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", tenant.key().as_ref(), user.key().as_ref()],
+        bump = car.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", tenant.key().as_ref(), user.key().as_ref()],
+        bump = car.state_bump,
+    )]
+    pub car: Account<'info, User>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// Returns unused funds from the vault to the user. Always allowed, even while paused.
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(error!(WithdrawError::InvalidWithdrawAmount));
+        }
+
+        // An open session still has to be paid from the vault
+        if self.car.is_parked {
+            return Err(error!(WithdrawError::CurrentlyParked));
+        }
+
+        if amount > self.car.available_balance || amount > self.vault.lamports() {
+            return Err(error!(WithdrawError::InsufficientVaultBalance));
+        }
+
+        if !can_pay_out(self.vault.lamports(), amount)? {
+            return Err(error!(WithdrawError::BelowRentReserve));
+        }
+
+        transfer_from_pda(
+            &self.system_program,
+            self.vault.to_account_info(),
+            self.user.to_account_info(),
+            &self.car.vault_seeds(),
+            amount,
+        )?;
+
+        // Update tracked amount to reflect current vault balance
+        self.car.amount = self.vault.lamports();
+        self.car
+            .debit_withdrawal(amount)
+            .ok_or_else(|| error!(WithdrawError::InsufficientVaultBalance))?;
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum WithdrawError {
+    #[msg("Withdraw amount must be greater than zero")]
+    InvalidWithdrawAmount,
+
+    #[msg("Cannot withdraw while parked")]
+    CurrentlyParked,
+
+    #[msg("Vault does not have enough balance")]
+    InsufficientVaultBalance,
+
+    #[msg("Withdrawal would leave the vault below its rent-exempt minimum")]
+    BelowRentReserve,
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::Fleet;
+
+#[derive(Accounts)]
+pub struct WithdrawFleet<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"fleet", fleet.tenant.as_ref(), admin.key().as_ref()],
+        bump = fleet.bump,
+    )]
+    pub fleet: Account<'info, Fleet>,
+
+    #[account(
+        mut,
+        seeds = [b"fleet_vault", fleet.key().as_ref()],
+        bump = fleet.vault_bump,
+    )]
+    pub fleet_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawFleet<'info> {
+    pub fn withdraw_fleet(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(error!(WithdrawFleetError::InvalidWithdrawAmount));
+        }
+
+        // Keep the fleet vault rent exempt
+        let reserve = Rent::get()?.minimum_balance(0);
+        let available = self.fleet_vault.lamports().saturating_sub(reserve);
+        if amount > available {
+            return Err(error!(WithdrawFleetError::InsufficientFleetBalance));
+        }
+
+        let fleet_key = self.fleet.key();
+        let seeds: &[&[u8]] = &[b"fleet_vault", fleet_key.as_ref(), &[self.fleet.vault_bump]];
+
+        transfer_from_pda(
+            &self.system_program,
+            self.fleet_vault.to_account_info(),
+            self.admin.to_account_info(),
+            seeds,
+            amount,
+        )
+    }
+}
+
+#[error_code]
+pub enum WithdrawFleetError {
+    #[msg("Withdraw amount must be greater than zero")]
+    InvalidWithdrawAmount,
+
+    #[msg("Fleet vault does not have enough balance")]
+    InsufficientFleetBalance,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::Profile;
+
+#[derive(Accounts)]
+pub struct WithdrawFromProfile<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", user.key().as_ref()],
+        bump = profile.bump,
+    )]
+    pub profile: Account<'info, Profile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile_vault", user.key().as_ref()],
+        bump = profile.vault_bump,
+    )]
+    pub profile_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawFromProfile<'info> {
+    pub fn withdraw_from_profile(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(error!(WithdrawFromProfileError::InvalidWithdrawAmount));
+        }
+
+        // Keep the profile vault rent exempt
+        let reserve = Rent::get()?.minimum_balance(0);
+        let available = self.profile_vault.lamports().saturating_sub(reserve);
+        if amount > available {
+            return Err(error!(WithdrawFromProfileError::InsufficientProfileBalance));
+        }
+
+        transfer_from_pda(
+            &self.system_program,
+            self.profile_vault.to_account_info(),
+            self.user.to_account_info(),
+            &self.profile.vault_seeds(),
+            amount,
+        )?;
+
+        // Update tracked amount to reflect current vault balance
+        self.profile.amount = self.profile_vault.lamports();
+
+        Ok(())
+    }
+}
+
+#[error_code]
+pub enum WithdrawFromProfileError {
+    #[msg("Withdraw amount must be greater than zero")]
+    InvalidWithdrawAmount,
+
+    #[msg("Profile vault does not have enough balance")]
+    InsufficientProfileBalance,
+}
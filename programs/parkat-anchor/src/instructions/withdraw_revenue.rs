@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::instructions::process_exit::transfer_from_pda;
+use crate::state::Tenant;
+
+#[derive(Accounts)]
+pub struct WithdrawRevenue<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"tenant", admin.key().as_ref()],
+        bump = tenant.bump,
+    )]
+    pub tenant: Account<'info, Tenant>,
+
+    #[account(
+        mut,
+        seeds = [b"revenue", tenant.key().as_ref()],
+        bump = tenant.revenue_bump,
+    )]
+    pub revenue: SystemAccount<'info>,
+
+    /// CHECK: Payout split PDA, only inspected to see whether the tenant configured one
+    #[account(
+        seeds = [b"payout_split", tenant.key().as_ref()],
+        bump,
+    )]
+    pub payout_split: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawRevenue<'info> {
+    pub fn withdraw_revenue(&mut self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Err(error!(WithdrawRevenueError::InvalidWithdrawAmount));
+        }
+
+        // Once a split is configured, revenue only leaves through `distribute_revenue`
+        if !self.payout_split.data_is_empty() {
+            return Err(error!(WithdrawRevenueError::PayoutSplitConfigured));
+        }
+
+        // The revenue PDA keeps its rent-exempt reserve and what open disputes and
+        // roaming settlements may still claim
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let available = self
+            .revenue
+            .lamports()
+            .saturating_sub(rent_exempt_minimum)
+            .saturating_sub(self.tenant.reserved_revenue());
+        if amount > available {
+            return Err(error!(WithdrawRevenueError::InsufficientRevenue));
+        }
+
+        let tenant_key = self.tenant.key();
+        transfer_from_pda(
+            &self.system_program,
+            self.revenue.to_account_info(),
+            self.admin.to_account_info(),
+            &[b"revenue", tenant_key.as_ref(), &[self.tenant.revenue_bump]],
+            amount,
+        )?;
+
+        emit!(RevenueWithdrawn {
+            tenant: tenant_key,
+            admin: self.admin.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+#[event]
+pub struct RevenueWithdrawn {
+    pub tenant: Pubkey,
+    pub admin: Pubkey,
+    pub amount: u64,
+}
+
+#[error_code]
+pub enum WithdrawRevenueError {
+    #[msg("Withdraw amount must be greater than zero")]
+    InvalidWithdrawAmount,
+
+    #[msg("Revenue balance is too low for this withdrawal")]
+    InsufficientRevenue,
+
+    #[msg("Revenue is paid out through the configured payout split")]
+    PayoutSplitConfigured,
+}
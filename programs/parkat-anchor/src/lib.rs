@@ -1,34 +1,272 @@
 use anchor_lang::prelude::*;
 
-mod instructions;
+pub mod instructions;
 pub mod state;
 use instructions::*;
+use state::*;
 
 declare_id!("FDKqFqZ8MnAfwVCGAR8FJfbSjHyfqs14Vx9c1hBZSjGU");
 
 
-#[program]
-pub mod parkat_anchor {
+pub use handlers::*;
+
+// Wraps `#[program]` so the allowance also covers the IDL instructions Anchor generates next
+// to it, which still call the deprecated `AccountInfo::realloc`
+#[allow(deprecated)]
+mod handlers {
     use super::*;
 
-    pub fn init_tenant(ctx: Context<InitTenant>, tenant_name: String) -> Result<()> {
-        ctx.accounts.init_tenant(&ctx.bumps, tenant_name)
-    }
+    #[program]
+    pub mod parkat_anchor {
+        use super::*;
 
-    pub fn init_user(ctx: Context<InitUser>, number_plate: String) -> Result<()> {
-        ctx.accounts.init_user(&ctx.bumps, number_plate)
-    }
+        pub fn init_tenant(ctx: Context<InitTenant>, tenant_name: String) -> Result<()> {
+            ctx.accounts.init_tenant(&ctx.bumps, tenant_name)
+        }
 
-    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
-        ctx.accounts.deposit(amount)
-    }
+        pub fn init_user(
+            ctx: Context<InitUser>,
+            number_plate: String,
+            proof: Vec<[u8; 32]>,
+        ) -> Result<()> {
+            ctx.accounts.init_user(&ctx.bumps, number_plate, proof)
+        }
 
-    pub fn record_parking_start(ctx: Context<RecordParkingStart>) -> Result<()> {
-        ctx.accounts.record_parking_start()
-    }
+        pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+            ctx.accounts.deposit(amount)
+        }
+
+        pub fn deposit_for(ctx: Context<DepositFor>, amount: u64) -> Result<()> {
+            ctx.accounts.deposit_for(amount)
+        }
+
+        pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+            ctx.accounts.withdraw(amount)
+        }
+
+        pub fn record_parking_start(ctx: Context<RecordParkingStart>, timestamp: Option<i64>) -> Result<()> {
+            ctx.accounts.record_parking_start(timestamp)
+        }
+
+        pub fn process_exit(ctx: Context<ProcessExit>, timestamp: Option<i64>) -> Result<()> {
+            ctx.accounts.process_exit(timestamp)
+        }
+
+        pub fn set_user_class(ctx: Context<SetUserClass>, class: UserClass) -> Result<()> {
+            ctx.accounts.set_user_class(class)
+        }
+
+        pub fn admin_force_exit(
+            ctx: Context<AdminForceExit>,
+            end_time: Option<i64>,
+            fee: Option<u64>,
+        ) -> Result<()> {
+            ctx.accounts.admin_force_exit(end_time, fee)
+        }
+
+        pub fn admin_adjust_session(ctx: Context<AdminAdjustSession>, start_time: i64) -> Result<()> {
+            ctx.accounts.admin_adjust_session(start_time)
+        }
+
+        pub fn set_overstay_policy(
+            ctx: Context<SetOverstayPolicy>,
+            max_duration_seconds: u64,
+            penalty: OverstayPenalty,
+        ) -> Result<()> {
+            ctx.accounts.set_overstay_policy(max_duration_seconds, penalty)
+        }
+
+        pub fn flag_overstay(ctx: Context<FlagOverstay>) -> Result<()> {
+            ctx.accounts.flag_overstay()
+        }
+
+        pub fn open_dispute(ctx: Context<OpenDispute>, reason: String) -> Result<()> {
+            ctx.accounts.open_dispute(&ctx.bumps, reason)
+        }
+
+        pub fn resolve_dispute(ctx: Context<ResolveDispute>, refund: u64) -> Result<()> {
+            ctx.accounts.resolve_dispute(refund)
+        }
+
+        pub fn init_platform_config(
+            ctx: Context<InitPlatformConfig>,
+            fee_bps: u16,
+            treasury: Pubkey,
+        ) -> Result<()> {
+            ctx.accounts.init_platform_config(&ctx.bumps, fee_bps, treasury)
+        }
+
+        pub fn init_payout_split(
+            ctx: Context<InitPayoutSplit>,
+            recipients: Vec<PayoutRecipient>,
+        ) -> Result<()> {
+            ctx.accounts.init_payout_split(&ctx.bumps, recipients)
+        }
+
+        pub fn set_payout_split(
+            ctx: Context<SetPayoutSplit>,
+            recipients: Vec<PayoutRecipient>,
+        ) -> Result<()> {
+            ctx.accounts.set_payout_split(recipients)
+        }
+
+        pub fn distribute_revenue<'info>(
+            ctx: Context<'_, '_, 'info, 'info, DistributeRevenue<'info>>,
+        ) -> Result<()> {
+            ctx.accounts.distribute_revenue(ctx.remaining_accounts)
+        }
+
+        pub fn withdraw_revenue(ctx: Context<WithdrawRevenue>, amount: u64) -> Result<()> {
+            ctx.accounts.withdraw_revenue(amount)
+        }
+
+        pub fn set_tenant_paused(ctx: Context<SetTenantPaused>, paused: bool) -> Result<()> {
+            ctx.accounts.set_tenant_paused(paused)
+        }
+
+        pub fn set_platform_paused(ctx: Context<SetPlatformPaused>, paused: bool) -> Result<()> {
+            ctx.accounts.set_platform_paused(paused)
+        }
+
+        pub fn init_tenant_registry(ctx: Context<InitTenantRegistry>) -> Result<()> {
+            ctx.accounts.init_tenant_registry(&ctx.bumps)
+        }
+
+        pub fn verify_tenant(ctx: Context<VerifyTenant>) -> Result<()> {
+            ctx.accounts.verify_tenant()
+        }
+
+        pub fn set_admission_mode(
+            ctx: Context<SetAdmissionMode>,
+            admission_mode: AdmissionMode,
+            allowlist_root: [u8; 32],
+        ) -> Result<()> {
+            ctx.accounts.set_admission_mode(admission_mode, allowlist_root)
+        }
+
+        pub fn approve_user(ctx: Context<ApproveUser>) -> Result<()> {
+            ctx.accounts.approve_user()
+        }
+
+        pub fn suspend_user(ctx: Context<SuspendUser>) -> Result<()> {
+            ctx.accounts.suspend_user()
+        }
+
+        pub fn unsuspend_user(ctx: Context<SuspendUser>) -> Result<()> {
+            ctx.accounts.unsuspend_user()
+        }
+
+        pub fn set_delegate(
+            ctx: Context<SetDelegate>,
+            delegate: Pubkey,
+            expires_at: i64,
+            spend_limit: u64,
+        ) -> Result<()> {
+            ctx.accounts.set_delegate(delegate, expires_at, spend_limit)
+        }
+
+        pub fn init_fleet(ctx: Context<InitFleet>, name: String) -> Result<()> {
+            ctx.accounts.init_fleet(&ctx.bumps, name)
+        }
+
+        pub fn fund_fleet(ctx: Context<FundFleet>, amount: u64) -> Result<()> {
+            ctx.accounts.fund_fleet(amount)
+        }
+
+        pub fn withdraw_fleet(ctx: Context<WithdrawFleet>, amount: u64) -> Result<()> {
+            ctx.accounts.withdraw_fleet(amount)
+        }
+
+        pub fn set_fleet_member(ctx: Context<SetFleetMember>, monthly_limit: u64) -> Result<()> {
+            ctx.accounts.set_fleet_member(&ctx.bumps, monthly_limit)
+        }
+
+        pub fn remove_fleet_member(ctx: Context<RemoveFleetMember>) -> Result<()> {
+            ctx.accounts.remove_fleet_member()
+        }
+
+        pub fn init_profile(ctx: Context<InitProfile>, default_plate: String) -> Result<()> {
+            ctx.accounts.init_profile(&ctx.bumps, default_plate)
+        }
+
+        pub fn deposit_to_profile(ctx: Context<DepositToProfile>, amount: u64) -> Result<()> {
+            ctx.accounts.deposit_to_profile(amount)
+        }
+
+        pub fn withdraw_from_profile(ctx: Context<WithdrawFromProfile>, amount: u64) -> Result<()> {
+            ctx.accounts.withdraw_from_profile(amount)
+        }
+
+        pub fn set_accepts_profiles(ctx: Context<SetAcceptsProfiles>, accepts_profiles: bool) -> Result<()> {
+            ctx.accounts.set_accepts_profiles(accepts_profiles)
+        }
+
+        pub fn link_profile(ctx: Context<LinkProfile>) -> Result<()> {
+            ctx.accounts.link_profile()
+        }
+
+        pub fn unlink_profile(ctx: Context<LinkProfile>) -> Result<()> {
+            ctx.accounts.unlink_profile()
+        }
+
+        pub fn init_roaming_agreement(
+            ctx: Context<InitRoamingAgreement>,
+            settlement_fee_bps: u16,
+        ) -> Result<()> {
+            ctx.accounts.init_roaming_agreement(&ctx.bumps, settlement_fee_bps)
+        }
+
+        pub fn settle_roaming(ctx: Context<SettleRoaming>) -> Result<()> {
+            ctx.accounts.settle_roaming()
+        }
+
+        pub fn issue_receipt(ctx: Context<IssueReceipt>) -> Result<()> {
+            ctx.accounts.issue_receipt(&ctx.bumps)
+        }
+
+        pub fn verify_receipt(ctx: Context<VerifyReceipt>, commitment: [u8; 32]) -> Result<bool> {
+            ctx.accounts.verify_receipt(commitment)
+        }
+
+        pub fn migrate_tenant_v1_to_v2(ctx: Context<MigrateTenant>) -> Result<()> {
+            ctx.accounts.migrate_tenant_v1_to_v2(&ctx.bumps)
+        }
+
+        pub fn migrate_user_v1_to_v2(ctx: Context<MigrateUser>) -> Result<()> {
+            ctx.accounts.migrate_user_v1_to_v2()
+        }
+
+        pub fn reconcile_vault(ctx: Context<ReconcileVault>) -> Result<()> {
+            ctx.accounts.reconcile_vault()
+        }
+
+        pub fn set_rounding_mode(ctx: Context<SetRoundingMode>, rounding_mode: RoundingMode) -> Result<()> {
+            ctx.accounts.set_rounding_mode(rounding_mode)
+        }
+
+        pub fn set_timing_policy(
+            ctx: Context<SetTimingPolicy>,
+            timing_mode: TimingMode,
+            clock_skew_tolerance_seconds: u32,
+        ) -> Result<()> {
+            ctx.accounts.set_timing_policy(timing_mode, clock_skew_tolerance_seconds)
+        }
+
+        pub fn add_gate_key(ctx: Context<AddGateKey>, key: Pubkey) -> Result<()> {
+            ctx.accounts.add_gate_key(&ctx.bumps, key)
+        }
+
+        pub fn remove_gate_key(ctx: Context<RemoveGateKey>) -> Result<()> {
+            ctx.accounts.remove_gate_key()
+        }
+
+        pub fn set_operator(
+            ctx: Context<SetOperator>,
+            operator: Pubkey,
+            max_backdate_seconds: u32,
+        ) -> Result<()> {
+            ctx.accounts.set_operator(operator, max_backdate_seconds)
+        }
 
-    pub fn process_exit(ctx: Context<ProcessExit>) -> Result<()> {
-        ctx.accounts.process_exit()
     }
-   
-}
\ No newline at end of file
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::state::FeePayer;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Dispute {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    /// Id of the disputed session (`User.session_count` when it was finalized)
+    pub session_id: u64,
+    pub fee: u64,
+    /// Who paid the disputed fee, and so receives any refund
+    pub payer: FeePayer,
+    /// Tenant's share of the disputed fee, the most it can refund
+    pub tenant_amount: u64,
+    pub refunded: u64,
+    pub reason: [u8; 64],
+    pub status: DisputeStatus,
+    pub opened_at: i64,
+    pub resolved_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum DisputeStatus {
+    #[default]
+    Open,
+    Resolved,
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+/// Length of a fleet member's spending period (30 days)
+pub const FLEET_PERIOD_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Employer account whose shared vault pays for its members' parking
+#[account]
+#[derive(InitSpace)]
+pub struct Fleet {
+    pub admin: Pubkey,
+    pub tenant: Pubkey,
+    pub name: [u8; 32],
+    pub member_count: u32,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+/// Links a user to a fleet and tracks their spending against the monthly limit
+#[account]
+#[derive(InitSpace)]
+pub struct FleetMember {
+    pub fleet: Pubkey,
+    pub user: Pubkey,
+    pub monthly_limit: u64,
+    pub period_start: i64,
+    pub spent_this_period: u64,
+    pub bump: u8,
+}
+
+impl FleetMember {
+    /// Charges `amount` against the current period, starting a new period once
+    /// the previous one has run out. Returns `None` if the limit would be exceeded.
+    pub fn charge(&mut self, amount: u64, now: i64) -> Option<()> {
+        if now >= self.period_start.checked_add(FLEET_PERIOD_SECONDS)? {
+            self.period_start = now;
+            self.spent_this_period = 0;
+        }
+
+        let spent = self.spent_this_period.checked_add(amount)?;
+        if spent > self.monthly_limit {
+            return None;
+        }
+        self.spent_this_period = spent;
+
+        Some(())
+    }
+}
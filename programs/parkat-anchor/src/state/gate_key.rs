@@ -0,0 +1,107 @@
+use anchor_lang::{prelude::*, solana_program::ed25519_program};
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+
+/// Oldest gate ticket accepted, so offline gates can sync within a day
+pub const MAX_GATE_TICKET_AGE_SECONDS: i64 = 24 * 60 * 60;
+
+/// Size of one signature's offsets in Ed25519 precompile instruction data
+const ED25519_OFFSETS_SIZE: usize = 14;
+
+/// Offsets instruction index meaning "this same instruction"
+const ED25519_SAME_INSTRUCTION: u16 = u16::MAX;
+
+/// Gate device key registered by a tenant to sign check-in and check-out tickets
+#[account]
+#[derive(InitSpace)]
+pub struct GateKey {
+    pub tenant: Pubkey,
+    pub key: Pubkey,
+    pub bump: u8,
+}
+
+/// Which way a car passed the gate.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GateDirection {
+    Entry,
+    Exit,
+}
+
+/// Ticket signed off-chain by a gate device. The Borsh encoding of the ticket
+/// is the message verified by the Ed25519 precompile.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GateTicket {
+    pub tenant: Pubkey,
+    pub number_plate: [u8; 16],
+    pub timestamp: i64,
+    pub direction: GateDirection,
+}
+
+impl GateTicket {
+    /// Finds a ticket signed by `gate_key` in an Ed25519 precompile instruction
+    /// placed before the current instruction. The runtime rejects the whole
+    /// transaction if that signature is invalid, so only the signer and message
+    /// need to be checked here.
+    pub fn load(instructions_sysvar: &AccountInfo, gate_key: &Pubkey) -> Result<Option<Self>> {
+        let current_index = load_current_index_checked(instructions_sysvar)?;
+
+        for index in 0..current_index {
+            let instruction = load_instruction_at_checked(usize::from(index), instructions_sysvar)?;
+            if instruction.program_id != ed25519_program::ID {
+                continue;
+            }
+            if let Some(ticket) = Self::from_ed25519_data(&instruction.data, gate_key) {
+                return Ok(Some(ticket));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Parses the first ticket signed by `gate_key` out of Ed25519 precompile
+    /// instruction data. Only signatures whose key and message live in that
+    /// same instruction are considered.
+    pub fn from_ed25519_data(data: &[u8], gate_key: &Pubkey) -> Option<Self> {
+        let signature_count = usize::from(*data.first()?);
+
+        (0..signature_count).find_map(|i| {
+            let start = 2 + i * ED25519_OFFSETS_SIZE;
+            let offsets = data.get(start..start + ED25519_OFFSETS_SIZE)?;
+            let field = |n: usize| u16::from_le_bytes([offsets[2 * n], offsets[2 * n + 1]]);
+
+            let (signature_index, public_key_offset, public_key_index) = (field(1), field(2), field(3));
+            let (message_offset, message_size, message_index) = (field(4), field(5), field(6));
+            if signature_index != ED25519_SAME_INSTRUCTION
+                || public_key_index != ED25519_SAME_INSTRUCTION
+                || message_index != ED25519_SAME_INSTRUCTION
+            {
+                return None;
+            }
+
+            let public_key_start = usize::from(public_key_offset);
+            let public_key = data.get(public_key_start..public_key_start + 32)?;
+            if public_key != gate_key.as_ref() {
+                return None;
+            }
+
+            let message_start = usize::from(message_offset);
+            let message = data.get(message_start..message_start + usize::from(message_size))?;
+            Self::try_from_slice(message).ok()
+        })
+    }
+
+    /// Whether the ticket was issued for this tenant, plate and direction, at a
+    /// time that is not in the future and not older than `MAX_GATE_TICKET_AGE_SECONDS`.
+    pub fn is_valid_for(
+        &self,
+        tenant: &Pubkey,
+        number_plate: &[u8; 16],
+        direction: GateDirection,
+        now: i64,
+    ) -> bool {
+        self.tenant == *tenant
+            && self.number_plate == *number_plate
+            && self.direction == direction
+            && self.timestamp <= now
+            && now.saturating_sub(self.timestamp) <= MAX_GATE_TICKET_AGE_SECONDS
+    }
+}
@@ -1,5 +1,23 @@
 pub mod tenant;
 pub mod user_state;
+pub mod dispute;
+pub mod platform_config;
+pub mod payout_split;
+pub mod tenant_registry;
+pub mod fleet;
+pub mod profile;
+pub mod roaming;
+pub mod receipt;
+pub mod gate_key;
 
 pub use tenant::*;
 pub use user_state::*;
+pub use dispute::*;
+pub use platform_config::*;
+pub use payout_split::*;
+pub use tenant_registry::*;
+pub use fleet::*;
+pub use profile::*;
+pub use roaming::*;
+pub use receipt::*;
+pub use gate_key::*;
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::state::BPS_DENOMINATOR;
+
+/// Maximum number of revenue recipients per tenant
+pub const MAX_PAYOUT_RECIPIENTS: usize = 5;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PayoutSplit {
+    pub tenant: Pubkey,
+    #[max_len(MAX_PAYOUT_RECIPIENTS)]
+    pub recipients: Vec<PayoutRecipient>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PayoutRecipient {
+    pub wallet: Pubkey,
+    /// Share of distributed revenue, in basis points
+    pub share_bps: u16,
+}
+
+impl PayoutRecipient {
+    pub fn share_of(&self, amount: u64) -> Option<u64> {
+        amount
+            .checked_mul(u64::from(self.share_bps))?
+            .checked_div(BPS_DENOMINATOR)
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::state::BPS_DENOMINATOR;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PlatformConfig {
+    pub authority: Pubkey,
+    /// Share of every parking fee taken by the platform, in basis points
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    pub bump: u8,
+    /// Blocks new deposits, registrations and sessions at every tenant while set
+    pub paused: bool,
+}
+
+impl PlatformConfig {
+    /// Splits a parking fee into the tenant's and the platform's share.
+    pub fn split_fee(&self, fee: u64) -> Option<(u64, u64)> {
+        let platform_amount = fee
+            .checked_mul(u64::from(self.fee_bps))?
+            .checked_div(BPS_DENOMINATOR)?;
+        let tenant_amount = fee.checked_sub(platform_amount)?;
+
+        Some((tenant_amount, platform_amount))
+    }
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Cross-tenant identity holding a shared balance usable at every tenant that accepts profiles
+#[account]
+#[derive(InitSpace)]
+pub struct Profile {
+    pub owner: Pubkey,
+    /// Plate used when registering with a tenant without giving one
+    pub default_plate: [u8; 16],
+    /// Tracked balance of the profile vault
+    pub amount: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Profile {
+    /// Signer seeds of this profile's vault PDA.
+    pub fn vault_seeds(&self) -> [&[u8]; 3] {
+        [
+            b"profile_vault",
+            self.owner.as_ref(),
+            std::slice::from_ref(&self.vault_bump),
+        ]
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+/// Expense-claim receipt for a finalized parking session
+#[account]
+#[derive(InitSpace)]
+pub struct Receipt {
+    pub tenant: Pubkey,
+    pub user: Pubkey,
+    pub user_account: Pubkey,
+    pub tenant_name: [u8; 32],
+    pub number_plate: [u8; 16],
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fee: u64,
+    /// Session number of the receipted session (`User.session_count` when it was finalized)
+    pub receipt_number: u64,
+    /// sha256 over the receipt contents, see `Receipt::compute_commitment`
+    pub commitment: [u8; 32],
+    pub issued_at: i64,
+    pub bump: u8,
+}
+
+impl Receipt {
+    /// Deterministic hash of the receipted fields, so a receipt can be checked off-chain.
+    pub fn compute_commitment(&self) -> [u8; 32] {
+        hashv(&[
+            self.tenant.as_ref(),
+            self.user.as_ref(),
+            &self.tenant_name,
+            &self.number_plate,
+            &self.start_time.to_le_bytes(),
+            &self.end_time.to_le_bytes(),
+            &self.fee.to_le_bytes(),
+            &self.receipt_number.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
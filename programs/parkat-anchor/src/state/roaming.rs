@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::state::BPS_DENOMINATOR;
+
+/// Agreement letting the members of two tenants park at each other's lots.
+/// `tenant_a` is always the smaller of the two keys.
+#[account]
+#[derive(InitSpace)]
+pub struct RoamingAgreement {
+    pub tenant_a: Pubkey,
+    pub tenant_b: Pubkey,
+    /// Share of each roaming fee kept by the home tenant for collecting it
+    pub settlement_fee_bps: u16,
+    /// Lamports `tenant_a` owes `tenant_b` for its members' visits
+    pub a_owes_b: u64,
+    /// Lamports `tenant_b` owes `tenant_a` for its members' visits
+    pub b_owes_a: u64,
+    pub last_settled_at: i64,
+    pub bump: u8,
+}
+
+impl RoamingAgreement {
+    /// Whether this agreement is between `home` and `visited`.
+    pub fn covers(&self, home: &Pubkey, visited: &Pubkey) -> bool {
+        (self.tenant_a == *home && self.tenant_b == *visited)
+            || (self.tenant_a == *visited && self.tenant_b == *home)
+    }
+
+    /// Records that `home` collected `tenant_amount` for a visit to the other tenant.
+    /// Returns the receivable owed to the visited tenant after the settlement fee.
+    pub fn record_visit(&mut self, home: &Pubkey, tenant_amount: u64) -> Option<u64> {
+        let settlement_fee = tenant_amount
+            .checked_mul(u64::from(self.settlement_fee_bps))?
+            .checked_div(BPS_DENOMINATOR)?;
+        let receivable = tenant_amount.checked_sub(settlement_fee)?;
+
+        if *home == self.tenant_a {
+            self.a_owes_b = self.a_owes_b.checked_add(receivable)?;
+        } else {
+            self.b_owes_a = self.b_owes_a.checked_add(receivable)?;
+        }
+
+        Some(receivable)
+    }
+
+    /// Cancels out what the tenants owe each other, leaving at most one side owing.
+    /// Returns the amount cancelled on each side.
+    pub fn net(&mut self) -> u64 {
+        let offset = self.a_owes_b.min(self.b_owes_a);
+        self.a_owes_b -= offset;
+        self.b_owes_a -= offset;
+        offset
+    }
+}
@@ -1,4 +1,21 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+use crate::state::{UserClass, BPS_DENOMINATOR};
+
+/// Parking fee in lamports charged per full minute
+pub const RATE_PER_MINUTE: u64 = 100;
+
+/// Current `Tenant` layout version. Unversioned (v1) accounts read as 0.
+pub const TENANT_VERSION: u8 = 2;
+
+/// Nominal slot length used to turn slot counts into durations
+pub const MS_PER_SLOT: u64 = 400;
+
+/// Largest clock-skew tolerance a tenant may configure
+pub const MAX_CLOCK_SKEW_TOLERANCE_SECONDS: u32 = 600;
+
+/// Largest backdating window a tenant may configure
+pub const MAX_BACKDATE_SECONDS: u32 = 24 * 60 * 60;
 
 #[account]
 #[derive(InitSpace)]
@@ -7,4 +24,202 @@ pub struct Tenant {
     pub name: [u8; 32],
     pub created_at: i64,
     pub bump: u8,
+    /// Posted maximum stay, 0 means unlimited
+    pub max_duration_seconds: u64,
+    pub overstay_penalty: OverstayPenalty,
+    pub revenue_bump: u8,
+    /// Lamports collected into the revenue PDA, net of refunds
+    pub total_collected: u64,
+    /// Number of finalized parking sessions
+    pub session_count: u64,
+    /// Blocks new deposits, registrations and sessions while set
+    pub paused: bool,
+    /// Set by the platform authority once the operator has been vetted
+    pub verified: bool,
+    pub admission_mode: AdmissionMode,
+    /// Merkle root of allowed user pubkeys, used in `AdmissionMode::Allowlist`
+    pub allowlist_root: [u8; 32],
+    /// Users may pay from their cross-tenant `Profile` balance
+    pub accepts_profiles: bool,
+    /// Layout version, see `TENANT_VERSION`
+    pub version: u8,
+    /// How session durations are turned into billable time
+    pub rounding_mode: RoundingMode,
+    /// Exits up to this many seconds before the recorded start count as zero-length
+    pub clock_skew_tolerance_seconds: u32,
+    pub timing_mode: TimingMode,
+    /// Key allowed to co-sign backdated session times, unset disables backdating
+    pub operator: Pubkey,
+    /// How far back an operator may date a session start or end
+    pub max_backdate_seconds: u32,
+    /// Tenant's share of the fees under open disputes, held back for refunds
+    pub disputed_amount: u64,
+    /// Roaming fees collected for visits to other tenants and not yet settled
+    pub roaming_payable: u64,
+    /// Space for future fields, so they can be added without a realloc
+    pub reserved: [u8; 6],
+}
+
+/// How new users are admitted by `init_user`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum AdmissionMode {
+    /// Any wallet can register
+    #[default]
+    Open,
+    /// Users register as pending and must be approved by the admin
+    ApprovalRequired,
+    /// Users must prove membership in the tenant's allowlist Merkle tree
+    Allowlist,
+}
+
+/// Clock used to measure session durations.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum TimingMode {
+    /// `Clock::unix_timestamp`
+    #[default]
+    WallClock,
+    /// Slots elapsed since the session started, at `MS_PER_SLOT` each
+    Slot,
+}
+
+/// How `parking_fee` bills partial minutes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum RoundingMode {
+    /// Only full minutes are billed (59 seconds is free)
+    #[default]
+    FloorMinute,
+    /// Any started minute is billed in full
+    CeilMinute,
+    /// Minutes are rounded half up (30 seconds bills one minute)
+    NearestMinute,
+    /// Billed to the second, rounded down to whole lamports
+    PerSecond,
+}
+
+impl RoundingMode {
+    /// Base fee for `duration_seconds` at `rate_per_minute`. Non-decreasing in the duration.
+    pub fn base_fee(&self, duration_seconds: u64, rate_per_minute: u64) -> Option<u64> {
+        match self {
+            RoundingMode::FloorMinute => (duration_seconds / 60).checked_mul(rate_per_minute),
+            RoundingMode::CeilMinute => duration_seconds.div_ceil(60).checked_mul(rate_per_minute),
+            RoundingMode::NearestMinute => {
+                (duration_seconds.checked_add(30)? / 60).checked_mul(rate_per_minute)
+            }
+            RoundingMode::PerSecond => {
+                let fee = u128::from(duration_seconds) * u128::from(rate_per_minute) / 60;
+                u64::try_from(fee).ok()
+            }
+        }
+    }
+}
+
+/// Penalty applied to sessions longer than the tenant's maximum stay.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum OverstayPenalty {
+    #[default]
+    None,
+    /// Flat amount in lamports added to the fee
+    Flat { amount: u64 },
+    /// Fee is scaled by `multiplier_bps / 10_000` (e.g. 15_000 charges 1.5x)
+    Multiplier { multiplier_bps: u16 },
+}
+
+impl OverstayPenalty {
+    pub fn apply(&self, fee: u64) -> Option<u64> {
+        match self {
+            OverstayPenalty::None => Some(fee),
+            OverstayPenalty::Flat { amount } => fee.checked_add(*amount),
+            OverstayPenalty::Multiplier { multiplier_bps } => fee
+                .checked_mul(u64::from(*multiplier_bps))?
+                .checked_div(BPS_DENOMINATOR),
+        }
+    }
+}
+
+impl Tenant {
+    /// Verifies a Merkle proof of `user` against the tenant's allowlist root.
+    /// Leaves are `sha256(user)` and sibling pairs are hashed in sorted order.
+    pub fn is_allowlisted(&self, user: &Pubkey, proof: &[[u8; 32]]) -> bool {
+        let leaf = hashv(&[user.as_ref()]).to_bytes();
+
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                hashv(&[&node, sibling]).to_bytes()
+            } else {
+                hashv(&[sibling, &node]).to_bytes()
+            }
+        });
+
+        root == self.allowlist_root
+    }
+
+    /// Accounts for a finalized session and the tenant's share of its fee.
+    pub fn record_session(&mut self, collected: u64) -> Option<()> {
+        self.total_collected = self.total_collected.checked_add(collected)?;
+        self.session_count = self.session_count.checked_add(1)?;
+
+        Some(())
+    }
+
+    /// Revenue that has to stay in the revenue PDA for dispute refunds and roaming settlements.
+    pub fn reserved_revenue(&self) -> u64 {
+        self.disputed_amount.saturating_add(self.roaming_payable)
+    }
+
+    /// Whether a session of `duration_seconds` exceeds the posted maximum stay.
+    pub fn is_overstay(&self, duration_seconds: u64) -> bool {
+        self.max_duration_seconds > 0 && duration_seconds > self.max_duration_seconds
+    }
+
+    /// Length of a session that started at `start_time` / `start_slot`, measured with the
+    /// tenant's timing mode. Wall-clock time running up to `clock_skew_tolerance_seconds`
+    /// behind the start counts as zero; anything further behind returns `None`.
+    /// Sessions without a start slot are always measured by wall-clock time.
+    pub fn session_duration(
+        &self,
+        start_time: i64,
+        start_slot: u64,
+        now: i64,
+        current_slot: u64,
+    ) -> Option<u64> {
+        match self.timing_mode {
+            TimingMode::Slot if start_slot > 0 => {
+                let slots = current_slot.checked_sub(start_slot)?;
+                Some(slots.checked_mul(MS_PER_SLOT)? / 1_000)
+            }
+            _ => {
+                let elapsed = now.checked_sub(start_time)?;
+                if elapsed >= 0 {
+                    u64::try_from(elapsed).ok()
+                } else if elapsed >= -i64::from(self.clock_skew_tolerance_seconds) {
+                    Some(0)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Whether `signer` is the tenant's operator. Always false while no operator is set.
+    pub fn is_operator(&self, signer: &Pubkey) -> bool {
+        self.operator != Pubkey::default() && *signer == self.operator
+    }
+
+    /// Whether an operator may date a session event at `timestamp`: never in the
+    /// future and no more than `max_backdate_seconds` before `now`.
+    pub fn is_within_backdate_window(&self, timestamp: i64, now: i64) -> bool {
+        timestamp <= now && now.saturating_sub(timestamp) <= i64::from(self.max_backdate_seconds)
+    }
+
+    /// Computes the fee for a session of `duration_seconds`, including any overstay
+    /// penalty, after the user's class is applied.
+    pub fn parking_fee(&self, duration_seconds: u64, class: UserClass) -> Option<u64> {
+        let mut fee = self.rounding_mode.base_fee(duration_seconds, RATE_PER_MINUTE)?;
+
+        if self.is_overstay(duration_seconds) {
+            fee = self.overstay_penalty.apply(fee)?;
+        }
+
+        class.apply(fee)
+    }
 }
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Global list of verified tenants, grown by `verify_tenant`.
+#[account]
+pub struct TenantRegistry {
+    pub tenants: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl TenantRegistry {
+    /// Most tenants the registry can list. Every listing reads and rewrites the whole
+    /// registry, so the cap keeps `verify_tenant` within the compute budget.
+    pub const MAX_TENANTS: usize = 256;
+
+    /// Account space (including discriminator) for a registry holding `tenant_count` tenants.
+    pub fn space(tenant_count: usize) -> usize {
+        8 + 4 + 32 * tenant_count + 1
+    }
+}
@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+/// Basis point denominator used for fee multipliers.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Current `User` layout version. Unversioned (v1) accounts read as 0.
+pub const USER_VERSION: u8 = 2;
+
 #[account]
 #[derive(InitSpace)]
 pub struct User {
@@ -11,4 +17,161 @@ pub struct User {
     pub vault_bump: u8,
     pub state_bump: u8,
     pub number_plate: [u8; 16],
+    pub class: UserClass,
+    /// When enforcement flagged the current session as overstayed, 0 if not flagged
+    pub overstay_flagged_at: i64,
+    /// Number of finalized sessions, also the id of the last session
+    pub session_count: u64,
+    pub last_session: SessionRecord,
+    pub status: UserStatus,
+    /// Session key allowed to start and end sessions, `Pubkey::default()` if none
+    pub delegate: Pubkey,
+    pub delegate_expires_at: i64,
+    /// Maximum total fees the delegate may pay from the vault
+    pub delegate_spend_limit: u64,
+    pub delegate_spent: u64,
+    /// Linked cross-tenant profile, `Pubkey::default()` if none
+    pub profile: Pubkey,
+    /// Tenant visited during a roaming session, `Pubkey::default()` when parked at home
+    pub roaming_tenant: Pubkey,
+    /// Layout version, see `USER_VERSION`
+    pub version: u8,
+    /// Lamports credited to the vault: deposits and reconciled external transfers
+    pub total_deposited: u64,
+    /// Parking fees paid from the vault, net of refunds
+    pub total_spent: u64,
+    /// Vault lamports the user can spend or withdraw
+    pub available_balance: u64,
+    /// Slot the current session started in, 0 if unknown
+    pub start_slot: u64,
+    /// Who paid the last finalized session's fee
+    pub last_fee_payer: FeePayer,
+    /// Part of the last finalized session's fee the tenant kept after the platform's cut
+    pub last_tenant_amount: u64,
+    /// Space for future fields, so they can be added without a realloc
+    pub reserved: [u8; 23],
+}
+
+/// Account that paid a session's parking fee, and that a refund goes back to.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum FeePayer {
+    /// The user's own vault
+    #[default]
+    Vault,
+    /// The vault of a fleet the user belongs to
+    Fleet,
+    /// The vault of the user's cross-tenant profile
+    Profile,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum UserStatus {
+    #[default]
+    Active,
+    /// Registered under an approval-required tenant and waiting for the admin
+    PendingApproval,
+    /// Barred from starting sessions by the tenant admin, can still exit and withdraw
+    Suspended,
+}
+
+/// Summary of the most recently finalized parking session.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub struct SessionRecord {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fee: u64,
+}
+
+impl User {
+    /// Signer seeds of this user's vault PDA.
+    pub fn vault_seeds(&self) -> [&[u8]; 4] {
+        [
+            b"vault",
+            self.tenant.as_ref(),
+            self.user.as_ref(),
+            std::slice::from_ref(&self.vault_bump),
+        ]
+    }
+
+    /// Whether `signer` is an unexpired delegate of this user.
+    pub fn is_delegate(&self, signer: &Pubkey, now: i64) -> bool {
+        self.delegate != Pubkey::default() && *signer == self.delegate && now < self.delegate_expires_at
+    }
+
+    /// Whether `signer` may act for this user: the owner or a valid delegate.
+    pub fn is_authorized(&self, signer: &Pubkey, now: i64) -> bool {
+        *signer == self.user || self.is_delegate(signer, now)
+    }
+
+    /// Credits lamports that entered the vault.
+    pub fn credit_deposit(&mut self, amount: u64) -> Option<()> {
+        self.total_deposited = self.total_deposited.checked_add(amount)?;
+        self.available_balance = self.available_balance.checked_add(amount)?;
+        Some(())
+    }
+
+    /// Debits a parking fee paid from the vault.
+    pub fn debit_fee(&mut self, amount: u64) -> Option<()> {
+        self.available_balance = self.available_balance.checked_sub(amount)?;
+        self.total_spent = self.total_spent.checked_add(amount)?;
+        Some(())
+    }
+
+    /// Credits a refunded fee back to the vault. Sessions finalized before fee
+    /// payers were recorded may have been paid elsewhere, so `total_spent` stops at zero.
+    pub fn credit_refund(&mut self, amount: u64) -> Option<()> {
+        self.available_balance = self.available_balance.checked_add(amount)?;
+        self.total_spent = self.total_spent.saturating_sub(amount);
+        Some(())
+    }
+
+    /// Debits lamports withdrawn by the user.
+    pub fn debit_withdrawal(&mut self, amount: u64) -> Option<()> {
+        self.available_balance = self.available_balance.checked_sub(amount)?;
+        Some(())
+    }
+
+    /// Ends the current session and records it as the last finalized session,
+    /// along with who paid its fee and the tenant's share of it.
+    pub fn finish_session(&mut self, end_time: i64, fee: u64, payer: FeePayer, tenant_amount: u64) {
+        self.last_session = SessionRecord {
+            start_time: self.time_stamp,
+            end_time,
+            fee,
+        };
+        self.last_fee_payer = payer;
+        self.last_tenant_amount = tenant_amount;
+        self.session_count = self.session_count.saturating_add(1);
+
+        self.time_stamp = end_time;
+        self.is_parked = false;
+        self.overstay_flagged_at = 0;
+        self.roaming_tenant = Pubkey::default();
+        self.start_slot = 0;
+    }
+}
+
+/// Billing class assigned to a user by the tenant admin.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace)]
+pub enum UserClass {
+    /// Pays the full parking fee
+    #[default]
+    Standard,
+    /// Pays `multiplier_bps / 10_000` of the parking fee (e.g. disabled-badge holders)
+    Discounted { multiplier_bps: u16 },
+    /// Parks for free (e.g. emergency vehicles, VIP staff)
+    Exempt,
+}
+
+impl UserClass {
+    /// Applies the class discount or exemption to a parking fee.
+    pub fn apply(&self, fee: u64) -> Option<u64> {
+        match self {
+            UserClass::Standard => Some(fee),
+            UserClass::Discounted { multiplier_bps } => fee
+                .checked_mul(u64::from(*multiplier_bps))?
+                .checked_div(BPS_DENOMINATOR),
+            UserClass::Exempt => Some(0),
+        }
+    }
 }
@@ -0,0 +1,140 @@
+//! Account fixtures shared by the integration tests. Accounts are built by
+//! serializing the program's own types, so variable-size enum fields such as
+//! `OverstayPenalty` and `UserClass` never shift the fields after them.
+#![allow(dead_code)]
+
+use anchor_lang::{ AccountDeserialize, AccountSerialize, AnchorDeserialize, Space };
+use mollusk_svm::{ program::loader_keys::LOADER_V3, result::InstructionResult, Mollusk };
+use solana_loader_v3_interface::state::UpgradeableLoaderState;
+use parkat_anchor::state::{ PlatformConfig, Tenant, User };
+use solana_sdk::{ account::Account, pubkey::Pubkey };
+
+/// Program id the tests load the program under
+pub fn program_id() -> Pubkey {
+    Pubkey::new_from_array(five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3"))
+}
+
+/// Mollusk running the built program
+pub fn mollusk() -> Mollusk {
+    Mollusk::new(&program_id(), "../../target/deploy/parkat_anchor")
+}
+
+/// The program's pubkey type for a test pubkey
+pub fn key(pubkey: &Pubkey) -> anchor_lang::prelude::Pubkey {
+    anchor_lang::prelude::Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+/// Account value with every field at its zero default, as a freshly created account reads
+pub fn zeroed<T: AnchorDeserialize + Space>() -> T {
+    T::deserialize(&mut &vec![0u8; T::INIT_SPACE][..]).unwrap()
+}
+
+/// Serialized account data for `value`, padded to `len` bytes
+pub fn account_data<T: AccountSerialize>(value: &T, len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(len);
+    value.try_serialize(&mut data).unwrap();
+    assert!(data.len() <= len, "account does not fit in {len} bytes");
+    data.resize(len, 0);
+    data
+}
+
+/// Rent-exempt account owned by the program holding `value` at its full allocated size
+pub fn program_account<T: AccountSerialize + Space>(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    value: &T
+) -> Account {
+    program_account_with_len(mollusk, program_id, value, 8 + T::INIT_SPACE)
+}
+
+/// Rent-exempt account owned by the program holding `value` in `len` bytes
+pub fn program_account_with_len<T: AccountSerialize>(
+    mollusk: &Mollusk,
+    program_id: &Pubkey,
+    value: &T,
+    len: usize
+) -> Account {
+    Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(len),
+        data: account_data(value, len),
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Deserializes a program account
+pub fn read<T: AccountDeserialize>(account: &Account) -> T {
+    T::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+/// Deserializes a program account as left by an instruction
+pub fn read_result<T: AccountDeserialize>(result: &InstructionResult, pubkey: &Pubkey) -> T {
+    read(result.get_account(pubkey).expect("account missing from result"))
+}
+
+/// Address of the upgradeable loader's program data account for `program_id`
+pub fn program_data_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[program_id.as_ref()], &LOADER_V3).0
+}
+
+/// Upgradeable loader program data account naming `upgrade_authority`
+pub fn program_data_account(mollusk: &Mollusk, upgrade_authority: Option<&Pubkey>) -> Account {
+    let data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: upgrade_authority.copied(),
+    }).unwrap();
+
+    Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+        data,
+        owner: LOADER_V3,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Tenant PDA for `admin`, and a zeroed tenant owned by `admin` with its bumps set
+pub fn tenant_fixture(admin: &Pubkey) -> (Pubkey, Tenant) {
+    let program_id = program_id();
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(&[b"tenant", admin.as_ref()], &program_id);
+    let (_, revenue_bump) = Pubkey::find_program_address(&[b"revenue", tenant_pda.as_ref()], &program_id);
+
+    let mut tenant: Tenant = zeroed();
+    tenant.admin = key(admin);
+    tenant.bump = tenant_bump;
+    tenant.revenue_bump = revenue_bump;
+
+    (tenant_pda, tenant)
+}
+
+/// User and vault PDAs for `user` at `tenant`, and a zeroed user account with its bumps set
+pub fn user_fixture(tenant: &Pubkey, user: &Pubkey) -> (Pubkey, Pubkey, User) {
+    let program_id = program_id();
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant.as_ref(), user.as_ref()],
+        &program_id
+    );
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant.as_ref(), user.as_ref()],
+        &program_id
+    );
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(user);
+    user_state.tenant = key(tenant);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    (user_pda, vault_pda, user_state)
+}
+
+/// Platform config PDA, and a zeroed config with its bump set
+pub fn platform_config_fixture() -> (Pubkey, PlatformConfig) {
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(&[b"platform"], &program_id());
+
+    let mut platform_config: PlatformConfig = zeroed();
+    platform_config.bump = platform_config_bump;
+
+    (platform_config_pda, platform_config)
+}
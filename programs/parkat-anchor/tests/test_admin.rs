@@ -0,0 +1,901 @@
+#[cfg(test)]
+use mollusk_svm::{ program, result::{ Check, InstructionResult }, Mollusk };
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+    instruction::{ AccountMeta, Instruction },
+    account::Account,
+    native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError,
+};
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use parkat_anchor::instructions::{
+    AdminAdjustSessionError,
+    AdminForceExitError,
+    RecordParkingStartError,
+    SetOverstayPolicyError,
+    SuspendUserError,
+    VerifyTenantError,
+    WithdrawRevenueError,
+};
+use parkat_anchor::state::{
+    OverstayPenalty,
+    PayoutRecipient,
+    PayoutSplit,
+    PlatformConfig,
+    Tenant,
+    TenantRegistry,
+    User,
+    UserClass,
+    UserStatus,
+};
+
+mod common;
+use common::{
+    key,
+    mollusk,
+    platform_config_fixture,
+    program_account,
+    program_account_with_len,
+    program_id,
+    read_result,
+    tenant_fixture,
+    user_fixture,
+    zeroed,
+};
+
+// Anchor custom error codes
+const ADMIN_FORCE_EXIT_FEE_EXCEEDS_COMPUTED_FEE: u32 =
+    AdminForceExitError::FeeExceedsComputedFee as u32 + ERROR_CODE_OFFSET;
+const ADMIN_ADJUST_SESSION_OVERLAPS_PREVIOUS_SESSION: u32 =
+    AdminAdjustSessionError::OverlapsPreviousSession as u32 + ERROR_CODE_OFFSET;
+const RECORD_PARKING_START_NOT_APPROVED: u32 =
+    RecordParkingStartError::NotApproved as u32 + ERROR_CODE_OFFSET;
+const RECORD_PARKING_START_SUSPENDED: u32 =
+    RecordParkingStartError::Suspended as u32 + ERROR_CODE_OFFSET;
+const SET_OVERSTAY_POLICY_INVALID_PENALTY_MULTIPLIER: u32 =
+    SetOverstayPolicyError::InvalidPenaltyMultiplier as u32 + ERROR_CODE_OFFSET;
+const SUSPEND_USER_NOT_ACTIVE: u32 = SuspendUserError::NotActive as u32 + ERROR_CODE_OFFSET;
+const VERIFY_TENANT_REGISTRY_FULL: u32 = VerifyTenantError::RegistryFull as u32 + ERROR_CODE_OFFSET;
+const WITHDRAW_REVENUE_PAYOUT_SPLIT_CONFIGURED: u32 =
+    WithdrawRevenueError::PayoutSplitConfigured as u32 + ERROR_CODE_OFFSET;
+
+#[test]
+fn test_set_user_class() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let user_wallet_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account with the default (standard) class
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+    ];
+
+    // Half price for disabled-badge holders
+    let class = UserClass::Discounted { multiplier_bps: 5_000 };
+    let data = parkat_anchor::instruction::SetUserClass { class }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), admin_account),
+        (tenant_pda, tenant_account),
+        (user.pubkey(), user_wallet_account),
+        (user_pda, user_pda_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.class, class);
+}
+
+/// Ends a ten-minute session for a user in `class` at a tenant with the given overstay policy,
+/// and returns the fee taken from the vault and credited to tenant revenue. The platform takes
+/// no cut.
+fn process_exit_with_policy(
+    class: UserClass,
+    max_duration_seconds: u64,
+    overstay_penalty: OverstayPenalty,
+) -> (u64, u64) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = 600;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let platform_treasury = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+    tenant_state.max_duration_seconds = max_duration_seconds;
+    tenant_state.overstay_penalty = overstay_penalty;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Parked since time 0
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.is_parked = true;
+    user_state.amount = vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.class = class;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    let revenue_balance = mollusk.sysvars.rent.minimum_balance(0);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: None }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(vault_balance, 0, &system_program)),
+        (user_pda, user_pda_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (revenue_pda, Account::new(revenue_balance, 0, &system_program)),
+        (platform_config_pda, platform_config_account),
+        (platform_treasury.pubkey(), Account::new(0, 0, &system_program)),
+        (system_program, system_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert!(!user_state.is_parked);
+
+    let vault_fee = vault_balance - result.get_account(&vault_pda).unwrap().lamports;
+    let revenue_fee = result.get_account(&revenue_pda).unwrap().lamports - revenue_balance;
+    assert_eq!(vault_balance - user_state.available_balance, vault_fee);
+
+    (vault_fee, revenue_fee)
+}
+
+/// Ends a ten-minute session for a user in `class` at a tenant without a maximum stay
+fn process_exit_for_class(class: UserClass) -> (u64, u64) {
+    process_exit_with_policy(class, 0, OverstayPenalty::None)
+}
+
+#[test]
+fn test_process_exit_standard_class() {
+    // 10 minutes at 100 lamports per minute
+    assert_eq!(process_exit_for_class(UserClass::Standard), (1_000, 1_000));
+}
+
+#[test]
+fn test_process_exit_discounted_class() {
+    assert_eq!(
+        process_exit_for_class(UserClass::Discounted { multiplier_bps: 5_000 }),
+        (500, 500)
+    );
+}
+
+#[test]
+fn test_process_exit_exempt_class() {
+    assert_eq!(process_exit_for_class(UserClass::Exempt), (0, 0));
+}
+
+#[test]
+fn test_process_exit_overstay_multiplier() {
+    // Ten minutes against a five-minute maximum stay bills 1_000 at 1.5x
+    assert_eq!(
+        process_exit_with_policy(
+            UserClass::Standard,
+            300,
+            OverstayPenalty::Multiplier { multiplier_bps: 15_000 },
+        ),
+        (1_500, 1_500)
+    );
+}
+
+#[test]
+fn test_process_exit_overstay_flat() {
+    assert_eq!(
+        process_exit_with_policy(UserClass::Standard, 300, OverstayPenalty::Flat { amount: 250 }),
+        (1_250, 1_250)
+    );
+}
+
+#[test]
+fn test_process_exit_within_max_stay() {
+    // The penalty only applies once the maximum stay is exceeded
+    assert_eq!(
+        process_exit_with_policy(UserClass::Standard, 600, OverstayPenalty::Flat { amount: 250 }),
+        (1_000, 1_000)
+    );
+}
+
+/// Sets a one-hour maximum stay with `penalty`
+fn set_overstay_policy(penalty: OverstayPenalty, checks: &[Check]) -> (Pubkey, InstructionResult) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let admin = Keypair::new();
+    let (tenant_pda, tenant_state) = tenant_fixture(&admin.pubkey());
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::SetOverstayPolicy {
+        max_duration_seconds: 3_600,
+        penalty,
+    }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+    (tenant_pda, result)
+}
+
+#[test]
+fn test_set_overstay_policy() {
+    let penalty = OverstayPenalty::Multiplier { multiplier_bps: 10_000 };
+    let (tenant_pda, result) = set_overstay_policy(penalty, &[Check::success()]);
+
+    let tenant_state: Tenant = read_result(&result, &tenant_pda);
+    assert_eq!(tenant_state.max_duration_seconds, 3_600);
+    assert_eq!(tenant_state.overstay_penalty, penalty);
+}
+
+#[test]
+fn test_set_overstay_policy_rejects_discounting_multiplier() {
+    // A multiplier below 1x would make overstaying cheaper than leaving on time
+    set_overstay_policy(
+        OverstayPenalty::Multiplier { multiplier_bps: 9_999 },
+        &[Check::err(ProgramError::Custom(SET_OVERSTAY_POLICY_INVALID_PENALTY_MULTIPLIER))],
+    );
+}
+
+/// Force-closes a session the user started at time 0
+fn admin_force_exit(end_time: Option<i64>, fee: Option<u64>, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Revenue PDA is funded to rent exemption when the tenant is created
+    let revenue_account = Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
+    let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let user_wallet_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account - user is stuck parked since the gate missed the exit
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.time_stamp = 0;
+    user_state.is_parked = true;
+    user_state.amount = vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let vault_account = Account::new(vault_balance, 0, &system_program);
+
+    // Platform config taking a 10% cut of every fee
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+    let platform_treasury = Keypair::new();
+    let fee_bps: u16 = 1_000;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.fee_bps = fee_bps;
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+    let platform_treasury_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::AdminForceExit { end_time, fee }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), admin_account),
+        (tenant_pda, tenant_account),
+        (user.pubkey(), user_wallet_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+        (revenue_pda, revenue_account),
+        (platform_config_pda, platform_config_account),
+        (platform_treasury.pubkey(), platform_treasury_account),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_admin_force_exit() {
+    // Close the session now and bill it from the recorded start time
+    admin_force_exit(None, None, &[Check::success()]);
+}
+
+#[test]
+fn test_admin_force_exit_fee_above_computed_fee() {
+    // A session closed the moment it started costs nothing, so no fee can be charged
+    admin_force_exit(
+        Some(0),
+        Some(1),
+        &[Check::err(ProgramError::Custom(ADMIN_FORCE_EXIT_FEE_EXCEEDS_COMPUTED_FEE))],
+    );
+}
+
+/// Moves the start of a session begun a day after the user's last exit to `start_time`,
+/// given relative to that exit
+fn admin_adjust_session(start_time: i64, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = 1_700_000_000;
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    // Tenant allows no operator backdating, which does not limit the admin's corrections
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    // User parked again a day after their last exit, ten minutes ago
+    let last_exit: i64 = 1_700_000_000 - 600 - 86_400;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.time_stamp = last_exit + 86_400;
+    user_state.is_parked = true;
+    user_state.state_bump = user_bump;
+    user_state.last_session.end_time = last_exit;
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::AdminAdjustSession { start_time: last_exit + start_time }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_admin_adjust_session_to_last_exit() {
+    admin_adjust_session(0, &[Check::success()]);
+}
+
+#[test]
+fn test_admin_adjust_session_before_last_exit() {
+    admin_adjust_session(
+        -1,
+        &[Check::err(ProgramError::Custom(ADMIN_ADJUST_SESSION_OVERLAPS_PREVIOUS_SESSION))],
+    );
+}
+
+/// Withdraws all collected revenue, with or without a payout split configured for the tenant
+fn withdraw_revenue(split_configured: bool, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (payout_split_pda, payout_split_bump) = Pubkey::find_program_address(
+        &[b"payout_split", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Revenue PDA holds its rent-exempt reserve plus collected fees
+    let collected_fees: u64 = 50_000;
+    let revenue_account = Account::new(
+        mollusk.sysvars.rent.minimum_balance(0) + collected_fees,
+        0,
+        &system_program,
+    );
+
+    // Create tenant account
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+    tenant_state.total_collected = collected_fees;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Without a split the PDA was never created
+    let payout_split_account = if split_configured {
+        let payout_split_state = PayoutSplit {
+            tenant: key(&tenant_pda),
+            recipients: vec![
+                PayoutRecipient { wallet: key(&Pubkey::new_unique()), share_bps: 10_000 },
+            ],
+            bump: payout_split_bump,
+        };
+        program_account(&mollusk, &program_id, &payout_split_state)
+    } else {
+        Account::default()
+    };
+
+    let instruction_accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(payout_split_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::WithdrawRevenue {
+        amount: collected_fees,
+    }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), admin_account),
+        (tenant_pda, tenant_account),
+        (revenue_pda, revenue_account),
+        (payout_split_pda, payout_split_account),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_withdraw_revenue() {
+    withdraw_revenue(false, &[Check::success()]);
+}
+
+#[test]
+fn test_withdraw_revenue_rejects_configured_payout_split() {
+    withdraw_revenue(
+        true,
+        &[Check::err(ProgramError::Custom(WITHDRAW_REVENUE_PAYOUT_SPLIT_CONFIGURED))],
+    );
+}
+
+/// Verifies a tenant against a registry already listing `registered_tenants` tenants
+fn verify_tenant(registered_tenants: usize, checks: &[Check]) -> (Pubkey, InstructionResult) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let authority = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (tenant_registry_pda, tenant_registry_bump) = Pubkey::find_program_address(
+        &[b"registry"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Platform config owned by the platform authority
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.authority = key(&authority.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Create unverified tenant account
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    let tenant_registry_state = TenantRegistry {
+        tenants: (0..registered_tenants).map(|_| key(&Pubkey::new_unique())).collect(),
+        bump: tenant_registry_bump,
+    };
+    let tenant_registry_account = program_account_with_len(
+        &mollusk,
+        &program_id,
+        &tenant_registry_state,
+        TenantRegistry::space(registered_tenants)
+    );
+
+    let instruction_accounts = vec![
+        AccountMeta::new(authority.pubkey(), true),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(tenant_registry_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::VerifyTenant {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (authority.pubkey(), authority_account),
+        (platform_config_pda, platform_config_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (tenant_registry_pda, tenant_registry_account),
+        (system_program, system_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    (tenant_registry_pda, result)
+}
+
+#[test]
+fn test_verify_tenant() {
+    let (tenant_registry_pda, result) = verify_tenant(1, &[Check::success()]);
+
+    let tenant_registry: TenantRegistry = read_result(&result, &tenant_registry_pda);
+    assert_eq!(tenant_registry.tenants.len(), 2);
+}
+
+#[test]
+fn test_verify_tenant_registry_full() {
+    verify_tenant(
+        TenantRegistry::MAX_TENANTS,
+        &[Check::err(ProgramError::Custom(VERIFY_TENANT_REGISTRY_FULL))],
+    );
+}
+
+/// Suspends a user account in `status`
+fn suspend_user_with_status(status: UserStatus, checks: &[Check]) -> (Pubkey, InstructionResult) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let user_wallet_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.status = status;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(admin.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::SuspendUser {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (admin.pubkey(), admin_account),
+        (tenant_pda, tenant_account),
+        (user.pubkey(), user_wallet_account),
+        (user_pda, user_pda_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    (user_pda, result)
+}
+
+#[test]
+fn test_suspend_user() {
+    let (user_pda, result) = suspend_user_with_status(UserStatus::Active, &[Check::success()]);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.status, UserStatus::Suspended);
+}
+
+#[test]
+fn test_suspend_pending_user() {
+    // Unsuspending restores `Active`, so a pending user must not be suspendable
+    suspend_user_with_status(
+        UserStatus::PendingApproval,
+        &[Check::err(ProgramError::Custom(SUSPEND_USER_NOT_ACTIVE))],
+    );
+}
+
+/// Starts a session for a funded user account in `status`
+fn record_parking_start_with_status(status: UserStatus, checks: &[Check]) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let tenant_admin = Keypair::new();
+    let user = Keypair::new();
+
+    let (tenant_pda, tenant_state) = tenant_fixture(&tenant_admin.pubkey());
+    let (user_pda, _, mut user_state) = user_fixture(&tenant_pda, &user.pubkey());
+    user_state.amount = LAMPORTS_PER_SOL;
+    user_state.available_balance = LAMPORTS_PER_SOL;
+    user_state.status = status;
+    let (platform_config_pda, platform_config_state) = platform_config_fixture();
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: None }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_record_parking_start_active_user() {
+    record_parking_start_with_status(UserStatus::Active, &[Check::success()]);
+}
+
+#[test]
+fn test_record_parking_start_suspended_user() {
+    record_parking_start_with_status(
+        UserStatus::Suspended,
+        &[Check::err(ProgramError::Custom(RECORD_PARKING_START_SUSPENDED))],
+    );
+}
+
+#[test]
+fn test_record_parking_start_pending_user() {
+    record_parking_start_with_status(
+        UserStatus::PendingApproval,
+        &[Check::err(ProgramError::Custom(RECORD_PARKING_START_NOT_APPROVED))],
+    );
+}
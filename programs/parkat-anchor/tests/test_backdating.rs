@@ -0,0 +1,292 @@
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use parkat_anchor::instructions::ProcessExitError;
+use mollusk_svm::{ program, result::{ Check, InstructionResult }, Mollusk };
+use parkat_anchor::state::{ PlatformConfig, Tenant, User };
+use solana_sdk::{
+    account::Account,
+    instruction::{ AccountMeta, Instruction },
+    native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+};
+
+mod common;
+use common::{ key, program_account, read_result, zeroed };
+
+const NOW: i64 = 1_700_000_000;
+
+// Anchor custom error codes
+const PROCESS_EXIT_INVALID_PARKING_DURATION: u32 =
+    ProcessExitError::InvalidParkingDuration as u32 + ERROR_CODE_OFFSET;
+
+/// Tenant with every field at its default, letting `operator` backdate up to `max_backdate_seconds`
+fn tenant_with(operator: &Pubkey, max_backdate_seconds: u32) -> Tenant {
+    let mut tenant: Tenant = zeroed();
+    tenant.operator = key(operator);
+    tenant.max_backdate_seconds = max_backdate_seconds;
+    tenant
+}
+
+#[test]
+fn test_only_the_operator_may_backdate() {
+    let operator = Pubkey::new_unique();
+    let tenant = tenant_with(&operator, 600);
+
+    assert!(tenant.is_operator(&key(&operator)));
+    assert!(!tenant.is_operator(&key(&Pubkey::new_unique())));
+
+    // Without an operator nobody can backdate, not even the default key
+    let tenant = tenant_with(&Pubkey::default(), 600);
+    assert!(!tenant.is_operator(&key(&Pubkey::default())));
+}
+
+#[test]
+fn test_backdate_window() {
+    let tenant = tenant_with(&Pubkey::new_unique(), 600);
+
+    assert!(tenant.is_within_backdate_window(NOW, NOW));
+    assert!(tenant.is_within_backdate_window(NOW - 600, NOW));
+    assert!(!tenant.is_within_backdate_window(NOW - 601, NOW));
+    assert!(!tenant.is_within_backdate_window(NOW + 1, NOW));
+
+    // A zero window only allows the current time
+    let tenant = tenant_with(&Pubkey::new_unique(), 0);
+    assert!(tenant.is_within_backdate_window(NOW, NOW));
+    assert!(!tenant.is_within_backdate_window(NOW - 1, NOW));
+}
+
+#[test]
+fn test_record_parking_start_backdated_by_operator() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = NOW;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let operator = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let operator_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Tenant lets its operator backdate up to 15 minutes
+    let mut tenant_state = tenant_with(&operator.pubkey(), 900);
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Optional roaming and gate ticket accounts are skipped with the program id as placeholder
+    let instruction_accounts = |operator_is_signer: bool| vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(operator.pubkey(), operator_is_signer),
+    ];
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
+        (operator.pubkey(), operator_account),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+        (system_program, system_account),
+    ];
+
+    // The car entered ten minutes before the start reached the chain
+    let entry_time = NOW - 600;
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: Some(entry_time) }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts(true));
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, &[Check::success()]);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.time_stamp, entry_time);
+    assert!(user_state.is_parked);
+
+    // Without the operator's signature the explicit time is rejected
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts(false));
+    let result = mollusk.process_instruction(&instruction, &tx_accounts);
+    assert!(result.program_result.is_err());
+
+    // So is a start older than the tenant's window
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: Some(NOW - 901) }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts(true));
+    let result = mollusk.process_instruction(&instruction, &tx_accounts);
+    assert!(result.program_result.is_err());
+}
+
+/// Runs `process_exit` for a session started at `start_time`, with the tenant's
+/// operator co-signing `exit_time` as the end of the session
+fn process_exit_backdated_to(
+    start_time: i64,
+    exit_time: i64,
+    checks: &[Check]
+) -> (Pubkey, InstructionResult) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = NOW;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let operator = Keypair::new();
+    let platform_treasury = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let rent_reserve = mollusk.sysvars.rent.minimum_balance(0);
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+
+    let mut tenant_state = tenant_with(&operator.pubkey(), 900);
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.time_stamp = start_time;
+    user_state.is_parked = true;
+    user_state.amount = rent_reserve + vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    // Optional fleet, profile, roaming and gate ticket accounts are skipped with
+    // the program id as placeholder
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(operator.pubkey(), true),
+    ];
+
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: Some(exit_time) }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(rent_reserve + vault_balance, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (revenue_pda, Account::new(rent_reserve, 0, &system_program)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (platform_treasury.pubkey(), Account::new(0, 0, &system_program)),
+        (system_program, system_account),
+        (operator.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    (user_pda, result)
+}
+
+#[test]
+fn test_process_exit_backdated_by_operator() {
+    // The session ends when the car left, not when the exit reached the chain
+    let (user_pda, result) = process_exit_backdated_to(NOW - 600, NOW - 120, &[Check::success()]);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert!(!user_state.is_parked);
+    assert_eq!(user_state.last_session.start_time, NOW - 600);
+    assert_eq!(user_state.last_session.end_time, NOW - 120);
+}
+
+#[test]
+fn test_process_exit_backdated_before_start() {
+    // Clock skew tolerance only covers the validator clock, never an operator's time
+    process_exit_backdated_to(
+        NOW - 600,
+        NOW - 601,
+        &[Check::err(ProgramError::Custom(PROCESS_EXIT_INVALID_PARKING_DURATION))],
+    );
+}
@@ -0,0 +1,486 @@
+use anchor_lang::{ error::ERROR_CODE_OFFSET, prelude::AccountInfo, AnchorSerialize, InstructionData };
+use parkat_anchor::instructions::ProcessExitError;
+use mollusk_svm::{ program, result::{ Check, InstructionResult }, Mollusk };
+use parkat_anchor::state::{
+    GateDirection,
+    GateKey,
+    GateTicket,
+    PlatformConfig,
+    Tenant,
+    User,
+    MAX_GATE_TICKET_AGE_SECONDS,
+};
+use solana_sdk::program_error::ProgramError;
+use solana_sdk::{
+    account::Account,
+    instruction::{ AccountMeta, Instruction },
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+};
+
+const NOW: i64 = 1_700_000_000;
+const PLATE: [u8; 16] = *b"LAG-123-XY\0\0\0\0\0\0";
+
+mod common;
+use common::{ key, program_account, read_result, zeroed };
+
+// Anchor custom error codes
+const PROCESS_EXIT_INVALID_PARKING_DURATION: u32 =
+    ProcessExitError::InvalidParkingDuration as u32 + ERROR_CODE_OFFSET;
+
+fn ticket(tenant: &Pubkey, direction: GateDirection, timestamp: i64) -> GateTicket {
+    GateTicket {
+        tenant: key(tenant),
+        number_plate: PLATE,
+        timestamp,
+        direction,
+    }
+}
+
+/// Ed25519 precompile instruction data carrying one signature, with the key,
+/// signature and message all stored in the instruction itself
+fn ed25519_instruction_data(gate: &Keypair, ticket: &GateTicket) -> Vec<u8> {
+    let mut message = Vec::new();
+    ticket.serialize(&mut message).unwrap();
+    let signature = gate.sign_message(&message);
+
+    let public_key_offset: u16 = 2 + 14;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1u8, 0];
+    for field in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(gate.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(&message);
+    data
+}
+
+/// Instructions sysvar data for `instructions`, executing the one at `current_index`
+fn instructions_sysvar_data(instructions: &[&Instruction], current_index: u16) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&(instructions.len() as u16).to_le_bytes());
+    data.resize(2 + 2 * instructions.len(), 0);
+
+    for (i, instruction) in instructions.iter().enumerate() {
+        let offset = data.len() as u16;
+        data[2 + 2 * i..4 + 2 * i].copy_from_slice(&offset.to_le_bytes());
+
+        data.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+        for meta in &instruction.accounts {
+            data.push(u8::from(meta.is_signer) | (u8::from(meta.is_writable) << 1));
+            data.extend_from_slice(meta.pubkey.as_ref());
+        }
+        data.extend_from_slice(instruction.program_id.as_ref());
+        data.extend_from_slice(&(instruction.data.len() as u16).to_le_bytes());
+        data.extend_from_slice(&instruction.data);
+    }
+
+    data.extend_from_slice(&current_index.to_le_bytes());
+    data
+}
+
+fn ed25519_program_id() -> Pubkey {
+    Pubkey::new_from_array(anchor_lang::solana_program::ed25519_program::ID.to_bytes())
+}
+
+fn instructions_sysvar_id() -> Pubkey {
+    Pubkey::new_from_array(solana_instructions_sysvar::ID.to_bytes())
+}
+
+#[test]
+fn test_ticket_parsed_from_ed25519_data() {
+    let gate = Keypair::new();
+    let tenant = Pubkey::new_unique();
+    let signed = ticket(&tenant, GateDirection::Entry, NOW - 300);
+
+    let data = ed25519_instruction_data(&gate, &signed);
+
+    let parsed = GateTicket::from_ed25519_data(&data, &key(&gate.pubkey()));
+    assert_eq!(parsed, Some(signed));
+}
+
+#[test]
+fn test_ticket_from_other_key_ignored() {
+    let gate = Keypair::new();
+    let other_gate = Keypair::new();
+    let tenant = Pubkey::new_unique();
+
+    let data = ed25519_instruction_data(&other_gate, &ticket(&tenant, GateDirection::Entry, NOW));
+
+    assert_eq!(GateTicket::from_ed25519_data(&data, &key(&gate.pubkey())), None);
+}
+
+#[test]
+fn test_ticket_referencing_other_instruction_ignored() {
+    let gate = Keypair::new();
+    let tenant = Pubkey::new_unique();
+    let mut data = ed25519_instruction_data(&gate, &ticket(&tenant, GateDirection::Entry, NOW));
+
+    // Point the message at another instruction, which this program never checks
+    data[14..16].copy_from_slice(&0u16.to_le_bytes());
+
+    assert_eq!(GateTicket::from_ed25519_data(&data, &key(&gate.pubkey())), None);
+}
+
+#[test]
+fn test_ticket_loaded_from_instructions_sysvar() {
+    let gate = Keypair::new();
+    let tenant = Pubkey::new_unique();
+    let signed = ticket(&tenant, GateDirection::Exit, NOW - 60);
+
+    let ed25519_instruction = Instruction::new_with_bytes(
+        ed25519_program_id(),
+        &ed25519_instruction_data(&gate, &signed),
+        vec![]
+    );
+    let exit_instruction = Instruction::new_with_bytes(Pubkey::new_unique(), &[], vec![]);
+
+    let sysvar_key = key(&instructions_sysvar_id());
+    let owner = key(&Pubkey::new_unique());
+    let gate_key = key(&gate.pubkey());
+
+    // Ticket verified before the current instruction
+    let mut lamports = 0;
+    let mut data = instructions_sysvar_data(&[&ed25519_instruction, &exit_instruction], 1);
+    let sysvar = AccountInfo::new(&sysvar_key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert_eq!(GateTicket::load(&sysvar, &gate_key).unwrap(), Some(signed));
+
+    // Verifications after the current instruction are not looked at
+    let mut lamports = 0;
+    let mut data = instructions_sysvar_data(&[&exit_instruction, &ed25519_instruction], 0);
+    let sysvar = AccountInfo::new(&sysvar_key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert_eq!(GateTicket::load(&sysvar, &gate_key).unwrap(), None);
+
+    // Only the real instructions sysvar is accepted
+    let fake_key = key(&Pubkey::new_unique());
+    let mut lamports = 0;
+    let mut data = instructions_sysvar_data(&[&ed25519_instruction, &exit_instruction], 1);
+    let sysvar = AccountInfo::new(&fake_key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    assert!(GateTicket::load(&sysvar, &gate_key).is_err());
+}
+
+#[test]
+fn test_ticket_validity() {
+    let tenant = Pubkey::new_unique();
+    let tenant_key = key(&tenant);
+    let entry = ticket(&tenant, GateDirection::Entry, NOW - 600);
+
+    assert!(entry.is_valid_for(&tenant_key, &PLATE, GateDirection::Entry, NOW));
+
+    // Wrong tenant, plate or direction
+    let other_tenant = key(&Pubkey::new_unique());
+    assert!(!entry.is_valid_for(&other_tenant, &PLATE, GateDirection::Entry, NOW));
+    assert!(!entry.is_valid_for(&tenant_key, &[0u8; 16], GateDirection::Entry, NOW));
+    assert!(!entry.is_valid_for(&tenant_key, &PLATE, GateDirection::Exit, NOW));
+
+    // Never from the future, never older than the maximum age
+    let future = ticket(&tenant, GateDirection::Entry, NOW + 1);
+    assert!(!future.is_valid_for(&tenant_key, &PLATE, GateDirection::Entry, NOW));
+
+    let oldest = ticket(&tenant, GateDirection::Entry, NOW - MAX_GATE_TICKET_AGE_SECONDS);
+    assert!(oldest.is_valid_for(&tenant_key, &PLATE, GateDirection::Entry, NOW));
+
+    let expired = ticket(&tenant, GateDirection::Entry, NOW - MAX_GATE_TICKET_AGE_SECONDS - 1);
+    assert!(!expired.is_valid_for(&tenant_key, &PLATE, GateDirection::Entry, NOW));
+}
+
+#[test]
+fn test_record_parking_start_with_gate_ticket() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = NOW;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let gate = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (gate_key_pda, gate_key_bump) = Pubkey::find_program_address(
+        &[b"gate_key", tenant_pda.as_ref(), gate.pubkey().as_ref()],
+        &program_id
+    );
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.number_plate = PLATE;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Gate registered with the tenant
+    let gate_key_state = GateKey {
+        tenant: key(&tenant_pda),
+        key: key(&gate.pubkey()),
+        bump: gate_key_bump,
+    };
+
+    let gate_key_account = program_account(&mollusk, &program_id, &gate_key_state);
+
+    // The car entered ten minutes before the ticket reached the chain
+    let entry_time = NOW - 600;
+    let ed25519_instruction = Instruction::new_with_bytes(
+        ed25519_program_id(),
+        &ed25519_instruction_data(&gate, &ticket(&tenant_pda, GateDirection::Entry, entry_time)),
+        vec![]
+    );
+
+    // Optional roaming accounts are skipped with the program id as placeholder
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(gate_key_pda, false),
+        AccountMeta::new_readonly(instructions_sysvar_id(), false),
+    ];
+
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: None }.data();
+    let start_instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let instructions_sysvar_account = Account {
+        lamports: 0,
+        data: instructions_sysvar_data(&[&ed25519_instruction, &start_instruction], 1),
+        owner: Pubkey::new_from_array(anchor_lang::solana_program::sysvar::ID.to_bytes()),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
+        (gate_key_pda, gate_key_account),
+        (instructions_sysvar_id(), instructions_sysvar_account),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+        (system_program, system_account),
+    ];
+
+    // The session starts at the ticket time, not when the transaction landed
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (&ed25519_instruction, &[Check::success()]),
+            (&start_instruction, &[Check::success()]),
+        ],
+        &tx_accounts,
+    );
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.time_stamp, entry_time);
+    assert!(user_state.is_parked);
+}
+
+/// Runs `process_exit` for a session started at `start_time`, ended by an exit
+/// ticket signed for `exit_time`
+fn process_exit_with_exit_ticket(
+    start_time: i64,
+    exit_time: i64,
+    checks: &[Check]
+) -> (Pubkey, InstructionResult) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    mollusk.sysvars.clock.unix_timestamp = NOW;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let gate = Keypair::new();
+    let platform_treasury = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+    let (gate_key_pda, gate_key_bump) = Pubkey::find_program_address(
+        &[b"gate_key", tenant_pda.as_ref(), gate.pubkey().as_ref()],
+        &program_id
+    );
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let rent_reserve = mollusk.sysvars.rent.minimum_balance(0);
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.time_stamp = start_time;
+    user_state.is_parked = true;
+    user_state.amount = rent_reserve + vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.number_plate = PLATE;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let gate_key_state = GateKey {
+        tenant: key(&tenant_pda),
+        key: key(&gate.pubkey()),
+        bump: gate_key_bump,
+    };
+
+    let ed25519_instruction = Instruction::new_with_bytes(
+        ed25519_program_id(),
+        &ed25519_instruction_data(&gate, &ticket(&tenant_pda, GateDirection::Exit, exit_time)),
+        vec![]
+    );
+
+    // Optional fleet, profile, roaming and operator accounts are skipped with the
+    // program id as placeholder
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(gate_key_pda, false),
+        AccountMeta::new_readonly(instructions_sysvar_id(), false),
+        AccountMeta::new_readonly(program_id, false),
+    ];
+
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: None }.data();
+    let exit_instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let instructions_sysvar_account = Account {
+        lamports: 0,
+        data: instructions_sysvar_data(&[&ed25519_instruction, &exit_instruction], 1),
+        owner: Pubkey::new_from_array(anchor_lang::solana_program::sysvar::ID.to_bytes()),
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(rent_reserve + vault_balance, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (revenue_pda, Account::new(rent_reserve, 0, &system_program)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (platform_treasury.pubkey(), Account::new(0, 0, &system_program)),
+        (system_program, system_account),
+        (gate_key_pda, program_account(&mollusk, &program_id, &gate_key_state)),
+        (instructions_sysvar_id(), instructions_sysvar_account),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+    ];
+
+    let result = mollusk.process_and_validate_instruction_chain(
+        &[
+            (&ed25519_instruction, &[Check::success()]),
+            (&exit_instruction, checks),
+        ],
+        &tx_accounts,
+    );
+
+    (user_pda, result)
+}
+
+#[test]
+fn test_process_exit_with_gate_ticket() {
+    // The session ends at the ticket time, not when the transaction landed
+    let (user_pda, result) = process_exit_with_exit_ticket(NOW - 600, NOW - 120, &[Check::success()]);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert!(!user_state.is_parked);
+    assert_eq!(user_state.last_session.start_time, NOW - 600);
+    assert_eq!(user_state.last_session.end_time, NOW - 120);
+}
+
+#[test]
+fn test_process_exit_rejects_ticket_before_start() {
+    // Clock skew tolerance only covers the validator clock, never a signed exit time
+    process_exit_with_exit_ticket(
+        NOW - 600,
+        NOW - 601,
+        &[Check::err(ProgramError::Custom(PROCESS_EXIT_INVALID_PARKING_DURATION))],
+    );
+}
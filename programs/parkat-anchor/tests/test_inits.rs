@@ -4,13 +4,20 @@ use solana_sdk::{
     pubkey::Pubkey,
     signature::{ Keypair, Signer },
     instruction::{ AccountMeta, Instruction },
-    account::{ Account, WritableAccount },
+    account::Account,
     native_token::LAMPORTS_PER_SOL,
-    rent::Rent,
-    sysvar::Sysvar,
+    program_error::ProgramError,
 };
-use anchor_lang::InstructionData;
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use parkat_anchor::instructions::{ InitPlatformConfigError, InitUserError };
 
+mod common;
+use common::{ key, program_account, program_data_account, program_data_address, zeroed };
+
+// Anchor custom error codes
+const INIT_PLATFORM_CONFIG_UNAUTHORIZED: u32 =
+    InitPlatformConfigError::Unauthorized as u32 + ERROR_CODE_OFFSET;
+const INIT_USER_NOT_ALLOWLISTED: u32 = InitUserError::NotAllowlisted as u32 + ERROR_CODE_OFFSET;
 
 #[test]
 fn test_init_tenant() {
@@ -27,17 +34,24 @@ fn test_init_tenant() {
         &program_id
     );
 
+    let (revenue_pda, _revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
     let (system_program, system_account) = program::keyed_account_for_system_program();
 
     // Build the accounts
-    let admin_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
     
     let tenant_account = Account::new(0, 0, &system_program);
+    let revenue_account = Account::new(0, 0, &system_program);
 
     // Get the accounts meta
     let instruction_accounts = vec![
         AccountMeta::new(admin.pubkey(), true),
         AccountMeta::new(tenant_pda, false),
+        AccountMeta::new(revenue_pda, false),
         AccountMeta::new_readonly(system_program, false)
     ];
    
@@ -51,6 +65,7 @@ fn test_init_tenant() {
     let tx_accounts = vec![
         (admin.pubkey(), admin_account),
         (tenant_pda, tenant_account),
+        (revenue_pda, revenue_account),
         (system_program, system_account)
     ];
 
@@ -72,6 +87,7 @@ fn test_init_user() {
 
     let user = Keypair::new();
     let tenant_admin = Keypair::new();
+    let sponsor = Keypair::new();
 
     let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
         &[b"tenant", tenant_admin.pubkey().as_ref()],
@@ -91,63 +107,297 @@ fn test_init_user() {
     let (system_program, system_account) = program::keyed_account_for_system_program();
 
     // Build the accounts
-    let user_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
-    let tenant_admin_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    // Sponsor pays rent, so the user's wallet needs no SOL
+    let user_account = Account::new(0, 0, &system_program);
+    let sponsor_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
     
     // Create tenant account with proper discriminator
-    use anchor_lang::Discriminator;
-    use parkat_anchor::state::Tenant;
-    
-    let mut tenant_data = vec![0u8; 8 + 73];
+    use parkat_anchor::state::{PlatformConfig, Tenant};
     
+    let mut tenant_state: Tenant = zeroed();
+
     // Set the discriminator (first 8 bytes)
-    tenant_data[0..8].copy_from_slice(&Tenant::DISCRIMINATOR);
-    
+
     // Serialize the tenant fields manually
     // Format: admin (32) + name (32) + created_at (8) + bump (1)
-    tenant_data[8..40].copy_from_slice(tenant_admin.pubkey().as_ref());
+    tenant_state.admin = key(&tenant_admin.pubkey());
     // name is [0u8; 32] - already zeros
     // created_at is 0 - already zeros
-    tenant_data[80] = tenant_bump; // bump is at position 8 + 32 + 32 + 8 = 80
-    
-    let tenant_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(tenant_data.len()),
-        data: tenant_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
     
     let user_account_data = Account::new(0, 0, &system_program);
     let vault_account = Account::new(0, 0, &system_program);
 
+    // Platform config (not paused)
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
     let instruction_accounts = vec![
-        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(sponsor.pubkey(), true),
         AccountMeta::new(tenant_pda, false),
         AccountMeta::new_readonly(tenant_admin.pubkey(), false),
         AccountMeta::new(user_account_pda, false),
         AccountMeta::new(vault_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
         AccountMeta::new_readonly(system_program, false),
     ];
 
     let data = parkat_anchor::instruction::InitUser {
         number_plate: String::from("ABC123"),
+        proof: vec![],
     }.data();
 
     let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
 
     let tx_accounts = vec![
         (user.pubkey(), user_account),
+        (sponsor.pubkey(), sponsor_account),
         (tenant_pda, tenant_account),
         (tenant_admin.pubkey(), tenant_admin_account),
         (user_account_pda, user_account_data),
         (vault_pda, vault_account),
+        (platform_config_pda, platform_config_account),
         (system_program, system_account),
     ];
 
+    // The sponsor also funds the vault up to rent exemption
     let _init_result = mollusk.process_and_validate_instruction(
         &instruction,
         &tx_accounts,
-        &[Check::success()],
+        &[
+            Check::success(),
+            Check::account(&vault_pda).lamports(mollusk.sysvars.rent.minimum_balance(0)).build(),
+        ],
+    );
+}
+/// Runs `init_platform_config` signed by `authority` against a program whose upgrade
+/// authority is `upgrade_authority`
+fn init_platform_config(authority: &Keypair, upgrade_authority: &Pubkey, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let platform_treasury = Keypair::new();
+
+    let (platform_config_pda, _platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+    let program_data_pda = program_data_address(&program_id);
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Build the accounts
+    let authority_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let platform_config_account = Account::new(0, 0, &system_program);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(authority.pubkey(), true),
+        AccountMeta::new(platform_config_pda, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_data_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    // Platform takes 2.5% of every parking fee
+    let data = parkat_anchor::instruction::InitPlatformConfig {
+        fee_bps: 250,
+        treasury: key(&platform_treasury.pubkey()),
+    }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (authority.pubkey(), authority_account),
+        (platform_config_pda, platform_config_account),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+        (program_data_pda, program_data_account(&mollusk, Some(upgrade_authority))),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_init_platform_config() {
+    let authority = Keypair::new();
+
+    init_platform_config(&authority, &authority.pubkey(), &[Check::success()]);
+}
+
+#[test]
+fn test_init_platform_config_not_upgrade_authority() {
+    let authority = Keypair::new();
+    let upgrade_authority = Keypair::new();
+
+    // Anyone other than the upgrade authority could otherwise claim the platform fee
+    init_platform_config(
+        &authority,
+        &upgrade_authority.pubkey(),
+        &[Check::err(ProgramError::Custom(INIT_PLATFORM_CONFIG_UNAUTHORIZED))],
+    );
+}
+
+/// Registers a user at an allowlist tenant, presenting the proof for `member`. The signing
+/// user is `member` when `listed`, otherwise a stranger reusing the member's proof.
+fn init_user_with_allowlist_proof(listed: bool, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let member = Keypair::new();
+    let user = if listed { member.insecure_clone() } else { Keypair::new() };
+    let colleague = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let sponsor = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_account_pda, _user_account_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, _vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
     );
-}
\ No newline at end of file
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Two-employee allowlist: leaves are sha256(pubkey), siblings hashed in sorted order
+    use anchor_lang::solana_program::hash::hashv;
+
+    let member_leaf = hashv(&[member.pubkey().as_ref()]).to_bytes();
+    let colleague_leaf = hashv(&[colleague.pubkey().as_ref()]).to_bytes();
+    let allowlist_root = hash_sorted_pair(&member_leaf, &colleague_leaf);
+
+    // Build the accounts
+    // Sponsor pays rent, so the user's wallet needs no SOL
+    let user_account = Account::new(0, 0, &system_program);
+    let sponsor_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    use parkat_anchor::state::{AdmissionMode, PlatformConfig, Tenant};
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.admission_mode = AdmissionMode::Allowlist;
+    tenant_state.allowlist_root = allowlist_root;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    let user_account_data = Account::new(0, 0, &system_program);
+    let vault_account = Account::new(0, 0, &system_program);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(sponsor.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_account_pda, false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::InitUser {
+        number_plate: String::from("ABC123"),
+        proof: vec![colleague_leaf],
+    }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (sponsor.pubkey(), sponsor_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_account_pda, user_account_data),
+        (vault_pda, vault_account),
+        (platform_config_pda, platform_config_account),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+}
+
+#[test]
+fn test_init_user_allowlisted() {
+    init_user_with_allowlist_proof(true, &[Check::success()]);
+}
+
+#[test]
+fn test_init_user_not_allowlisted() {
+    // The proof is valid for a member, but does not hash up to the root for anyone else
+    init_user_with_allowlist_proof(
+        false,
+        &[Check::err(ProgramError::Custom(INIT_USER_NOT_ALLOWLISTED))],
+    );
+}
+
+/// Hashes two Merkle nodes the way `Tenant::is_allowlisted` does, smaller node first
+fn hash_sorted_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    use anchor_lang::solana_program::hash::hashv;
+
+    if a <= b {
+        hashv(&[a, b]).to_bytes()
+    } else {
+        hashv(&[b, a]).to_bytes()
+    }
+}
+
+#[test]
+fn test_allowlist_proof_with_odd_leaf_count() {
+    use anchor_lang::solana_program::hash::hashv;
+    use parkat_anchor::state::Tenant;
+
+    // Three employees: the first two are paired, the last one is carried up unpaired
+    let members: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let leaves: Vec<[u8; 32]> = members
+        .iter()
+        .map(|member| hashv(&[member.as_ref()]).to_bytes())
+        .collect();
+    let first_pair = hash_sorted_pair(&leaves[0], &leaves[1]);
+
+    let mut tenant: Tenant = zeroed();
+    tenant.allowlist_root = hash_sorted_pair(&first_pair, &leaves[2]);
+
+    assert!(tenant.is_allowlisted(&key(&members[0]), &[leaves[1], leaves[2]]));
+    assert!(tenant.is_allowlisted(&key(&members[1]), &[leaves[0], leaves[2]]));
+
+    // The leaf at the last position proves membership with a single sibling
+    assert!(tenant.is_allowlisted(&key(&members[2]), &[first_pair]));
+
+    // Nobody else can reuse that proof
+    assert!(!tenant.is_allowlisted(&key(&Pubkey::new_unique()), &[first_pair]));
+}
@@ -0,0 +1,217 @@
+#[cfg(test)]
+use mollusk_svm::{ program, result::Check, Mollusk };
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+    instruction::{ AccountMeta, Instruction },
+    account::Account,
+    native_token::LAMPORTS_PER_SOL,
+};
+use anchor_lang::{ Discriminator, InstructionData, Space };
+use parkat_anchor::state::{ Tenant, User, TENANT_VERSION, USER_VERSION };
+
+mod common;
+use common::{ account_data, key, zeroed };
+
+/// Original `Tenant` size: admin, name, created_at, bump
+const TENANT_V1_SPACE: usize = 32 + 32 + 8 + 1;
+
+/// Original `User` size: user, tenant, time_stamp, is_parked, amount, vault_bump, state_bump, number_plate
+const USER_V1_SPACE: usize = 32 + 32 + 8 + 1 + 8 + 1 + 1 + 16;
+
+#[test]
+fn test_migrate_tenant_v1_to_v2() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let payer = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let payer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let revenue_account = Account::new(0, 0, &system_program);
+
+    // Tenant in the original layout
+    let created_at: i64 = 1_700_000_000;
+    let mut tenant_data = vec![0u8; 8 + TENANT_V1_SPACE];
+    tenant_data[0..8].copy_from_slice(Tenant::DISCRIMINATOR);
+    tenant_data[8..40].copy_from_slice(tenant_admin.pubkey().as_ref());
+    tenant_data[40..45].copy_from_slice(b"Jerry");
+    tenant_data[72..80].copy_from_slice(&created_at.to_le_bytes());
+    tenant_data[80] = tenant_bump;
+
+    let tenant_account = Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(tenant_data.len()),
+        data: tenant_data,
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    // Old fields are kept, new fields are zeroed except the revenue bump and the version
+    let new_len = 8 + Tenant::INIT_SPACE;
+    let mut expected_tenant: Tenant = zeroed();
+    expected_tenant.admin = key(&tenant_admin.pubkey());
+    expected_tenant.name[..5].copy_from_slice(b"Jerry");
+    expected_tenant.created_at = created_at;
+    expected_tenant.bump = tenant_bump;
+    expected_tenant.revenue_bump = revenue_bump;
+    expected_tenant.version = TENANT_VERSION;
+    let expected_data = account_data(&expected_tenant, new_len);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::MigrateTenantV1ToV2 {}.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (payer.pubkey(), payer_account),
+        (tenant_pda, tenant_account),
+        (revenue_pda, revenue_account),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&tenant_pda)
+                .space(new_len)
+                .lamports(mollusk.sysvars.rent.minimum_balance(new_len))
+                .data(&expected_data)
+                .build(),
+            Check::account(&revenue_pda)
+                .lamports(mollusk.sysvars.rent.minimum_balance(0))
+                .build(),
+        ],
+    );
+}
+
+/// Migrates a v1 user whose unfunded vault holds `deposits` lamports
+fn migrate_user_v1_to_v2(deposits: u64) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let payer = Keypair::new();
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, _tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let payer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // User in the original layout, whose vault holds nothing but deposits
+    let vault_account = Account::new(deposits, 0, &system_program);
+    let mut user_data = vec![0u8; 8 + USER_V1_SPACE];
+    user_data[0..8].copy_from_slice(User::DISCRIMINATOR);
+    user_data[8..40].copy_from_slice(user.pubkey().as_ref());
+    user_data[40..72].copy_from_slice(tenant_pda.as_ref());
+    user_data[81..89].copy_from_slice(&deposits.to_le_bytes());
+    user_data[89] = vault_bump;
+    user_data[90] = user_bump;
+    user_data[91..98].copy_from_slice(b"ABC-123");
+
+    let user_pda_account = Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(user_data.len()),
+        data: user_data,
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+
+    // Old fields are kept, the whole vault balance is credited to the ledger, the
+    // payer adds the vault's rent reserve on top, and the version is set
+    let new_len = 8 + User::INIT_SPACE;
+    let funded_vault = deposits + mollusk.sysvars.rent.minimum_balance(0);
+    let mut expected_user: User = zeroed();
+    expected_user.user = key(&user.pubkey());
+    expected_user.tenant = key(&tenant_pda);
+    expected_user.amount = funded_vault;
+    expected_user.vault_bump = vault_bump;
+    expected_user.state_bump = user_bump;
+    expected_user.number_plate[..7].copy_from_slice(b"ABC-123");
+    expected_user.total_deposited = deposits;
+    expected_user.available_balance = deposits;
+    expected_user.version = USER_VERSION;
+    let expected_data = account_data(&expected_user, new_len);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(payer.pubkey(), true),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::MigrateUserV1ToV2 {}.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (payer.pubkey(), payer_account),
+        (user_pda, user_pda_account),
+        (vault_pda, vault_account),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&user_pda)
+                .space(new_len)
+                .lamports(mollusk.sysvars.rent.minimum_balance(new_len))
+                .data(&expected_data)
+                .build(),
+            Check::account(&vault_pda).lamports(funded_vault).build(),
+        ],
+    );
+}
+
+#[test]
+fn test_migrate_user_v1_to_v2() {
+    migrate_user_v1_to_v2(2 * LAMPORTS_PER_SOL);
+}
+
+#[test]
+fn test_migrate_user_v1_to_v2_below_rent_reserve() {
+    // Deposits smaller than the rent reserve stay fully spendable
+    migrate_user_v1_to_v2(1_000);
+}
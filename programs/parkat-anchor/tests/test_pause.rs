@@ -0,0 +1,412 @@
+#[cfg(test)]
+use mollusk_svm::{ program, result::Check };
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+    instruction::{ AccountMeta, Instruction },
+    account::Account,
+    native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError,
+};
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use parkat_anchor::instructions::{
+    DepositError,
+    DepositToProfileError,
+    FundFleetError,
+    InitUserError,
+    RecordParkingStartError,
+};
+use parkat_anchor::state::{ Fleet, PlatformConfig, Profile, Tenant };
+
+mod common;
+use common::{
+    key,
+    mollusk,
+    platform_config_fixture,
+    program_account,
+    program_id,
+    tenant_fixture,
+    user_fixture,
+    zeroed,
+};
+
+// Anchor custom error codes
+const DEPOSIT_PAUSED: u32 = DepositError::Paused as u32 + ERROR_CODE_OFFSET;
+const INIT_USER_PAUSED: u32 = InitUserError::Paused as u32 + ERROR_CODE_OFFSET;
+const RECORD_PARKING_START_PAUSED: u32 = RecordParkingStartError::Paused as u32 + ERROR_CODE_OFFSET;
+const FUND_FLEET_PAUSED: u32 = FundFleetError::Paused as u32 + ERROR_CODE_OFFSET;
+const DEPOSIT_TO_PROFILE_PAUSED: u32 = DepositToProfileError::Paused as u32 + ERROR_CODE_OFFSET;
+
+/// Which switch froze the deployment
+#[derive(Clone, Copy)]
+enum Pause {
+    Tenant,
+    Platform,
+}
+
+/// Tenant owned by `tenant_admin` and the platform config, with `pause` switched on
+fn paused_fixtures(tenant_admin: &Pubkey, pause: Pause) -> (Pubkey, Tenant, Pubkey, PlatformConfig) {
+    let (tenant_pda, mut tenant_state) = tenant_fixture(tenant_admin);
+    let (platform_config_pda, mut platform_config_state) = platform_config_fixture();
+
+    match pause {
+        Pause::Tenant => tenant_state.paused = true,
+        Pause::Platform => platform_config_state.paused = true,
+    }
+
+    (tenant_pda, tenant_state, platform_config_pda, platform_config_state)
+}
+
+/// A funded wallet paying into one of the program's vaults, so only the pause can fail it
+fn funded_wallet() -> Account {
+    Account::new(10 * LAMPORTS_PER_SOL, 0, &program::keyed_account_for_system_program().0)
+}
+
+fn deposit_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+    let (user_pda, vault_pda, user_state) = user_fixture(&tenant_pda, &user.pubkey());
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::Deposit { amount: LAMPORTS_PER_SOL }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), funded_wallet()),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(0, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(DEPOSIT_PAUSED))],
+    );
+}
+
+fn deposit_for_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let funder = Keypair::new();
+    let beneficiary = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+    let (user_pda, vault_pda, user_state) = user_fixture(&tenant_pda, &beneficiary.pubkey());
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(funder.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(beneficiary.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::DepositFor { amount: LAMPORTS_PER_SOL }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (funder.pubkey(), funded_wallet()),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (beneficiary.pubkey(), Account::new(0, 0, &system_program)),
+        (vault_pda, Account::new(0, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(DEPOSIT_PAUSED))],
+    );
+}
+
+fn init_user_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+    let (user_pda, vault_pda, _) = user_fixture(&tenant_pda, &user.pubkey());
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::InitUser {
+        number_plate: String::from("ABC123"),
+        proof: vec![],
+    }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), funded_wallet()),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (user_pda, Account::new(0, 0, &system_program)),
+        (vault_pda, Account::new(0, 0, &system_program)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(INIT_USER_PAUSED))],
+    );
+}
+
+fn record_parking_start_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+    let (user_pda, _, mut user_state) = user_fixture(&tenant_pda, &user.pubkey());
+    user_state.amount = LAMPORTS_PER_SOL;
+    user_state.available_balance = LAMPORTS_PER_SOL;
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: None }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), funded_wallet()),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), funded_wallet()),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(RECORD_PARKING_START_PAUSED))],
+    );
+}
+
+fn fund_fleet_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let funder = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let fleet_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+
+    let (fleet_pda, fleet_bump) = Pubkey::find_program_address(
+        &[b"fleet", tenant_pda.as_ref(), fleet_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (fleet_vault_pda, fleet_vault_bump) = Pubkey::find_program_address(
+        &[b"fleet_vault", fleet_pda.as_ref()],
+        &program_id
+    );
+
+    let mut fleet_state: Fleet = zeroed();
+    fleet_state.admin = key(&fleet_admin.pubkey());
+    fleet_state.tenant = key(&tenant_pda);
+    fleet_state.bump = fleet_bump;
+    fleet_state.vault_bump = fleet_vault_bump;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(funder.pubkey(), true),
+        AccountMeta::new_readonly(fleet_pda, false),
+        AccountMeta::new(fleet_vault_pda, false),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::FundFleet { amount: LAMPORTS_PER_SOL }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (funder.pubkey(), funded_wallet()),
+        (fleet_pda, program_account(&mollusk, &program_id, &fleet_state)),
+        (fleet_vault_pda, Account::new(0, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(FUND_FLEET_PAUSED))],
+    );
+}
+
+fn deposit_to_profile_while(pause: Pause) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let owner = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state, platform_config_pda, platform_config_state) =
+        paused_fixtures(&tenant_admin.pubkey(), pause);
+
+    let (profile_pda, profile_bump) = Pubkey::find_program_address(
+        &[b"profile", owner.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (profile_vault_pda, profile_vault_bump) = Pubkey::find_program_address(
+        &[b"profile_vault", owner.pubkey().as_ref()],
+        &program_id
+    );
+
+    let mut profile_state: Profile = zeroed();
+    profile_state.owner = key(&owner.pubkey());
+    profile_state.bump = profile_bump;
+    profile_state.vault_bump = profile_vault_bump;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(owner.pubkey(), true),
+        AccountMeta::new(profile_pda, false),
+        AccountMeta::new(profile_vault_pda, false),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::DepositToProfile { amount: LAMPORTS_PER_SOL }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (owner.pubkey(), funded_wallet()),
+        (profile_pda, program_account(&mollusk, &program_id, &profile_state)),
+        (profile_vault_pda, Account::new(0, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::err(ProgramError::Custom(DEPOSIT_TO_PROFILE_PAUSED))],
+    );
+}
+
+#[test]
+fn test_deposit_while_tenant_paused() {
+    deposit_while(Pause::Tenant);
+}
+
+#[test]
+fn test_deposit_while_platform_paused() {
+    deposit_while(Pause::Platform);
+}
+
+#[test]
+fn test_deposit_for_while_tenant_paused() {
+    deposit_for_while(Pause::Tenant);
+}
+
+#[test]
+fn test_deposit_for_while_platform_paused() {
+    deposit_for_while(Pause::Platform);
+}
+
+#[test]
+fn test_init_user_while_tenant_paused() {
+    init_user_while(Pause::Tenant);
+}
+
+#[test]
+fn test_init_user_while_platform_paused() {
+    init_user_while(Pause::Platform);
+}
+
+#[test]
+fn test_record_parking_start_while_tenant_paused() {
+    record_parking_start_while(Pause::Tenant);
+}
+
+#[test]
+fn test_record_parking_start_while_platform_paused() {
+    record_parking_start_while(Pause::Platform);
+}
+
+#[test]
+fn test_fund_fleet_while_tenant_paused() {
+    fund_fleet_while(Pause::Tenant);
+}
+
+#[test]
+fn test_fund_fleet_while_platform_paused() {
+    fund_fleet_while(Pause::Platform);
+}
+
+#[test]
+fn test_deposit_to_profile_while_tenant_paused() {
+    deposit_to_profile_while(Pause::Tenant);
+}
+
+#[test]
+fn test_deposit_to_profile_while_platform_paused() {
+    deposit_to_profile_while(Pause::Platform);
+}
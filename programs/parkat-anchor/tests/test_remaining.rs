@@ -1,15 +1,27 @@
 #[cfg(test)]
-use mollusk_svm::{ program, result::Check, Mollusk };
+use mollusk_svm::{ program, result::{ Check, InstructionResult }, Mollusk };
 use solana_sdk::{
     pubkey::Pubkey,
     signature::{ Keypair, Signer },
     instruction::{ AccountMeta, Instruction },
-    account::{ Account, WritableAccount },
+    account::Account,
     native_token::LAMPORTS_PER_SOL,
-    rent::Rent,
-    sysvar::Sysvar,
+    program_error::ProgramError,
 };
-use anchor_lang::InstructionData;
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use anchor_lang::solana_program::system_instruction::SystemError;
+use parkat_anchor::instructions::{ DistributeRevenueError, ResolveDisputeError };
+use parkat_anchor::state::{ PayoutRecipient, PayoutSplit };
+
+mod common;
+use common::{ key, mollusk, program_account, program_id, read_result, tenant_fixture, zeroed };
+
+// Anchor framework and custom error codes
+const ACCOUNT_NOT_INITIALIZED: u32 = anchor_lang::error::ErrorCode::AccountNotInitialized as u32;
+const DISTRIBUTE_REVENUE_NOTHING_TO_DISTRIBUTE: u32 =
+    DistributeRevenueError::NothingToDistribute as u32 + ERROR_CODE_OFFSET;
+const RESOLVE_DISPUTE_REFUND_EXCEEDS_TENANT_SHARE: u32 =
+    ResolveDisputeError::RefundExceedsTenantShare as u32 + ERROR_CODE_OFFSET;
 
 #[test]
 fn test_deposit_by_user() {
@@ -45,49 +57,45 @@ fn test_deposit_by_user() {
 
     // Initialize Accounts
     let user_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
-    let tenant_admin_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
 
     // Create tenant account
-    use anchor_lang::Discriminator;
-    use parkat_anchor::state::{Tenant, User};
-    
-    let mut tenant_data = vec![0u8; 8 + 73];
-    tenant_data[0..8].copy_from_slice(&Tenant::DISCRIMINATOR);
-    tenant_data[8..40].copy_from_slice(tenant_admin.pubkey().as_ref());  // admin
-    tenant_data[80] = tenant_bump;  // bump at position 8 + 32 + 32 + 8 = 80
+    use parkat_anchor::state::{PlatformConfig, Tenant, User};
     
-    let tenant_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(tenant_data.len()),
-        data: tenant_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
 
     // Create User account
-    // User space: user(32) + tenant(32) + time_stamp(8) + is_parked(1) + amount(8) + vault_bump(1) + state_bump(1) + number_plate(16) = 99 bytes
-    let mut user_data = vec![0u8; 8 + 99];
-    user_data[0..8].copy_from_slice(&User::DISCRIMINATOR);
-    user_data[8..40].copy_from_slice(user.pubkey().as_ref());      // user pubkey
-    user_data[40..72].copy_from_slice(tenant_pda.as_ref());        // tenant pubkey
+    // User layout: user(32) + tenant(32) + time_stamp(8) + is_parked(1) + amount(8) + vault_bump(1) + state_bump(1) + number_plate(16), remaining fields default to zero
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
     // time_stamp = 0 (already zeros at positions 72-79)
     // is_parked = false (already 0 at position 80)
     // amount = 0 (already zeros at positions 81-88)
-    user_data[89] = vault_bump;   // vault_bump at position 8 + 32 + 32 + 8 + 1 + 8 = 89
-    user_data[90] = user_bump;    // state_bump at position 90
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
     // number_plate is zeros (already zeros at positions 91-106)
-    
-    let user_pda_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(user_data.len()),
-        data: user_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
 
     // Vault account (starts with 0 lamports, will receive deposit)
     let vault_account = Account::new(0, 0, &system_program);
 
+    // Platform config (not paused)
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
     // Build instruction accounts (order must match Deposit struct)
     let instruction_accounts = vec![
         AccountMeta::new(user.pubkey(), true),              
@@ -95,11 +103,12 @@ fn test_deposit_by_user() {
         AccountMeta::new_readonly(tenant_admin.pubkey(), false), 
         AccountMeta::new(vault_pda, false),                 
         AccountMeta::new(user_pda, false),                  
+        AccountMeta::new_readonly(platform_config_pda, false),
         AccountMeta::new_readonly(system_program, false),   
     ];
 
     // Create instruction data
-    let deposit_amount = 1 * LAMPORTS_PER_SOL;
+    let deposit_amount = LAMPORTS_PER_SOL;
     let data = parkat_anchor::instruction::Deposit {
         amount: deposit_amount,
     }.data();
@@ -113,6 +122,7 @@ fn test_deposit_by_user() {
         (tenant_admin.pubkey(), tenant_admin_account),
         (vault_pda, vault_account),
         (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
         (system_program, system_account),
     ];
 
@@ -148,7 +158,7 @@ fn test_record_parking_start() {
     );
 
     // Derive Vault PDA (not used in this instruction but needed for User account data)
-    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
         &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
         &program_id
     );
@@ -157,46 +167,42 @@ fn test_record_parking_start() {
     let (system_program, system_account) = program::keyed_account_for_system_program();
 
     // Initialize Accounts
-    let user_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
-    let tenant_admin_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
 
     // Create tenant account with proper discriminator
-    use anchor_lang::Discriminator;
-    use parkat_anchor::state::{Tenant, User};
+    use parkat_anchor::state::{PlatformConfig, Tenant, User};
     
-    let mut tenant_data = vec![0u8; 8 + 73];
-    tenant_data[0..8].copy_from_slice(&Tenant::DISCRIMINATOR);
-    tenant_data[8..40].copy_from_slice(tenant_admin.pubkey().as_ref());  // admin
-    tenant_data[80] = tenant_bump;  // bump
-    
-    let tenant_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(tenant_data.len()),
-        data: tenant_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
 
     // Create User account with proper discriminator
     // User is NOT parked initially (is_parked = false)
-    let mut user_data = vec![0u8; 8 + 99];
-    user_data[0..8].copy_from_slice(&User::DISCRIMINATOR);
-    user_data[8..40].copy_from_slice(user.pubkey().as_ref());      
-    user_data[40..72].copy_from_slice(tenant_pda.as_ref());        
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
     // time_stamp = 0 (positions 72-79)
-    user_data[80] = 0;  // is_parked = false
+    user_state.is_parked = false;
     // amount = 0 (positions 81-88)
-    user_data[89] = vault_bump;   
-    user_data[90] = user_bump;    
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
     // number_plate (positions 91-106)
-    
-    let user_pda_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(user_data.len()),
-        data: user_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Platform config (not paused)
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
 
     // Build instruction accounts 
     let instruction_accounts = vec![
@@ -204,10 +210,11 @@ fn test_record_parking_start() {
         AccountMeta::new(tenant_pda, false),                        
         AccountMeta::new_readonly(tenant_admin.pubkey(), false),    
         AccountMeta::new(user_pda, false),                          
+        AccountMeta::new_readonly(platform_config_pda, false),
     ];
 
     // Create instruction data 
-    let data = parkat_anchor::instruction::RecordParkingStart {}.data();
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: None }.data();
 
     // Create the instruction
     let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
@@ -217,6 +224,7 @@ fn test_record_parking_start() {
         (tenant_pda, tenant_account),
         (tenant_admin.pubkey(), tenant_admin_account),
         (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
         (system_program, system_account),
     ];
 
@@ -238,7 +246,6 @@ fn test_process_exit() {
 
     let user = Keypair::new();
     let tenant_admin = Keypair::new();
-    let admin_wallet = Keypair::new();
 
     let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
         &[b"tenant", tenant_admin.pubkey().as_ref()],
@@ -255,56 +262,64 @@ fn test_process_exit() {
         &program_id
     );
 
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
     let (system_program, system_account) = program::keyed_account_for_system_program();
 
+    // Revenue PDA is funded to rent exemption when the tenant is created
+    let revenue_account = Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
     // Initialize Accounts
-    let user_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
-    let tenant_admin_account = Account::new(1 * LAMPORTS_PER_SOL, 0, &system_program);
-    let admin_wallet_account = Account::new(0, 0, &system_program);
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let platform_treasury_account = Account::new(0, 0, &system_program);
 
     // Create tenant account
-    use anchor_lang::Discriminator;
-    use parkat_anchor::state::{Tenant, User};
+    use parkat_anchor::state::{PlatformConfig, Tenant, User};
     
-    let mut tenant_data = vec![0u8; 8 + 73];
-    tenant_data[0..8].copy_from_slice(&Tenant::DISCRIMINATOR);
-    tenant_data[8..40].copy_from_slice(tenant_admin.pubkey().as_ref());
-    tenant_data[80] = tenant_bump;
-    
-    let tenant_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(tenant_data.len()),
-        data: tenant_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
 
     // Create User account - user is parked with a timestamp
     let parking_start_time: i64 = 0; // Some past timestamp
-    let mut user_data = vec![0u8; 8 + 99];
-    user_data[0..8].copy_from_slice(&User::DISCRIMINATOR);
-    user_data[8..40].copy_from_slice(user.pubkey().as_ref());      
-    user_data[40..72].copy_from_slice(tenant_pda.as_ref());        
-    // time_stamp (positions 72-79) - set parking start time
-    user_data[72..80].copy_from_slice(&parking_start_time.to_le_bytes());
-    user_data[80] = 1;  // is_parked = true
-    // amount (positions 81-88) - set to vault balance
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
     let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
-    user_data[81..89].copy_from_slice(&vault_balance.to_le_bytes());
-    user_data[89] = vault_bump;   
-    user_data[90] = user_bump;    
-    
-    let user_pda_account = Account {
-        lamports: mollusk.sysvars.rent.minimum_balance(user_data.len()),
-        data: user_data,
-        owner: program_id,
-        executable: false,
-        rent_epoch: 0,
-    };
+    user_state.time_stamp = parking_start_time;
+    user_state.is_parked = true;
+    user_state.amount = vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
 
     // Vault account with balance (user has deposited funds)
     let vault_account = Account::new(vault_balance, 0, &system_program);
 
+    // Platform config taking a 10% cut of every fee
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+    let platform_treasury = Keypair::new();
+    let fee_bps: u16 = 1_000;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.fee_bps = fee_bps;
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
     // Build instruction accounts 
     let instruction_accounts = vec![
         AccountMeta::new(user.pubkey(), true),                      
@@ -312,12 +327,14 @@ fn test_process_exit() {
         AccountMeta::new(user_pda, false),                          
         AccountMeta::new(tenant_pda, false),                        
         AccountMeta::new_readonly(tenant_admin.pubkey(), false),    
-        AccountMeta::new(admin_wallet.pubkey(), false),             
+        AccountMeta::new(revenue_pda, false),             
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
         AccountMeta::new_readonly(system_program, false),           
     ];
 
     // Create instruction data 
-    let data = parkat_anchor::instruction::ProcessExit {}.data();
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: None }.data();
 
     // Create the instruction
     let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
@@ -328,7 +345,9 @@ fn test_process_exit() {
         (user_pda, user_pda_account),
         (tenant_pda, tenant_account),
         (tenant_admin.pubkey(), tenant_admin_account),
-        (admin_wallet.pubkey(), admin_wallet_account),
+        (revenue_pda, revenue_account),
+        (platform_config_pda, platform_config_account),
+        (platform_treasury.pubkey(), platform_treasury_account),
         (system_program, system_account),
     ];
 
@@ -337,4 +356,1549 @@ fn test_process_exit() {
         &tx_accounts,
         &[Check::success()],
     );
-}
\ No newline at end of file
+}
+#[test]
+fn test_process_exit_charged_to_fleet() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    // Ten minutes after the session started
+    mollusk.sysvars.clock.unix_timestamp = 600;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Revenue PDA is funded to rent exemption when the tenant is created
+    let revenue_account = Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
+    // Initialize Accounts
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let platform_treasury_account = Account::new(0, 0, &system_program);
+
+    // Create tenant account
+    use parkat_anchor::state::{Fleet, FleetMember, PlatformConfig, Tenant, User};
+    
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account - user is parked with a timestamp
+    let parking_start_time: i64 = 0; // Some past timestamp
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    user_state.time_stamp = parking_start_time;
+    user_state.is_parked = true;
+    user_state.amount = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Vault account with balance (user has deposited funds)
+    let vault_account = Account::new(vault_balance, 0, &system_program);
+
+    // Platform config taking a 10% cut of every fee
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+    let platform_treasury = Keypair::new();
+    let fee_bps: u16 = 1_000;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.fee_bps = fee_bps;
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Employer fleet covering the user with a 1 SOL monthly limit
+    let employer = Keypair::new();
+    let (fleet_pda, fleet_bump) = Pubkey::find_program_address(
+        &[b"fleet", tenant_pda.as_ref(), employer.pubkey().as_ref()],
+        &program_id
+    );
+    let (fleet_vault_pda, fleet_vault_bump) = Pubkey::find_program_address(
+        &[b"fleet_vault", fleet_pda.as_ref()],
+        &program_id
+    );
+    let (fleet_member_pda, fleet_member_bump) = Pubkey::find_program_address(
+        &[b"fleet_member", fleet_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let mut fleet_state: Fleet = zeroed();
+    fleet_state.admin = key(&employer.pubkey());
+    fleet_state.tenant = key(&tenant_pda);
+    fleet_state.member_count = 1;
+    fleet_state.bump = fleet_bump;
+    fleet_state.vault_bump = fleet_vault_bump;
+
+    let fleet_account = program_account(&mollusk, &program_id, &fleet_state);
+
+    let monthly_limit: u64 = LAMPORTS_PER_SOL;
+    let mut fleet_member_state: FleetMember = zeroed();
+    fleet_member_state.fleet = key(&fleet_pda);
+    fleet_member_state.user = key(&user.pubkey());
+    fleet_member_state.monthly_limit = monthly_limit;
+    fleet_member_state.bump = fleet_member_bump;
+
+    let fleet_member_account = program_account(&mollusk, &program_id, &fleet_member_state);
+
+    let fleet_vault_balance = mollusk.sysvars.rent.minimum_balance(0) + LAMPORTS_PER_SOL;
+    let fleet_vault_account = Account::new(fleet_vault_balance, 0, &system_program);
+
+    // Build instruction accounts 
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),                      
+        AccountMeta::new(vault_pda, false),                         
+        AccountMeta::new(user_pda, false),                          
+        AccountMeta::new(tenant_pda, false),                        
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),    
+        AccountMeta::new(revenue_pda, false),             
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),           
+        AccountMeta::new_readonly(fleet_pda, false),
+        AccountMeta::new(fleet_vault_pda, false),
+        AccountMeta::new(fleet_member_pda, false),
+    ];
+
+    // Create instruction data 
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: None }.data();
+
+    // Create the instruction
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (revenue_pda, revenue_account),
+        (platform_config_pda, platform_config_account),
+        (platform_treasury.pubkey(), platform_treasury_account),
+        (system_program, system_account),
+        (fleet_pda, fleet_account),
+        (fleet_vault_pda, fleet_vault_account),
+        (fleet_member_pda, fleet_member_account),
+    ];
+
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            // 10 minutes at 100 lamports per minute come out of the fleet vault
+            Check::account(&fleet_vault_pda).lamports(fleet_vault_balance - 1_000).build(),
+            Check::account(&vault_pda).lamports(vault_balance).build(),
+        ],
+    );
+}
+#[test]
+fn test_process_exit_charged_to_profile() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+    // Ten minutes after the session started
+    mollusk.sysvars.clock.unix_timestamp = 600;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Revenue PDA is funded to rent exemption when the tenant is created
+    let revenue_account = Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
+    // Initialize Accounts
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    // Rent exempt, so the small platform cut can land in it
+    let platform_treasury_account = Account::new(mollusk.sysvars.rent.minimum_balance(0), 0, &system_program);
+
+    // Cross-tenant profile linked to the user
+    let (profile_pda, profile_bump) = Pubkey::find_program_address(
+        &[b"profile", user.pubkey().as_ref()],
+        &program_id
+    );
+    let (profile_vault_pda, profile_vault_bump) = Pubkey::find_program_address(
+        &[b"profile_vault", user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // Create tenant account
+    use parkat_anchor::state::{FeePayer, PlatformConfig, Profile, Tenant, User};
+    
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+    tenant_state.accepts_profiles = true;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account - user is parked with a timestamp
+    let parking_start_time: i64 = 0; // Some past timestamp
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    user_state.time_stamp = parking_start_time;
+    user_state.is_parked = true;
+    user_state.amount = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.profile = key(&profile_pda);
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Vault account with balance (user has deposited funds)
+    let vault_account = Account::new(vault_balance, 0, &system_program);
+
+    // Platform config taking a 10% cut of every fee
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+    let platform_treasury = Keypair::new();
+    let fee_bps: u16 = 1_000;
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.fee_bps = fee_bps;
+    platform_config_state.treasury = key(&platform_treasury.pubkey());
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    let profile_vault_balance = mollusk.sysvars.rent.minimum_balance(0) + LAMPORTS_PER_SOL;
+    let mut profile_state: Profile = zeroed();
+    profile_state.owner = key(&user.pubkey());
+    profile_state.amount = profile_vault_balance;
+    profile_state.bump = profile_bump;
+    profile_state.vault_bump = profile_vault_bump;
+
+    let profile_account = program_account(&mollusk, &program_id, &profile_state);
+
+    let profile_vault_account = Account::new(profile_vault_balance, 0, &system_program);
+
+    // Build instruction accounts 
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),                      
+        AccountMeta::new(vault_pda, false),                         
+        AccountMeta::new(user_pda, false),                          
+        AccountMeta::new(tenant_pda, false),                        
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),    
+        AccountMeta::new(revenue_pda, false),             
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new(platform_treasury.pubkey(), false),
+        AccountMeta::new_readonly(system_program, false),           
+        // No fleet
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new(profile_pda, false),
+        AccountMeta::new(profile_vault_pda, false),
+    ];
+
+    // Create instruction data 
+    let data = parkat_anchor::instruction::ProcessExit { timestamp: None }.data();
+
+    // Create the instruction
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (revenue_pda, revenue_account),
+        (platform_config_pda, platform_config_account),
+        (platform_treasury.pubkey(), platform_treasury_account),
+        (system_program, system_account),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+        (profile_pda, profile_account),
+        (profile_vault_pda, profile_vault_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            // 10 minutes at 100 lamports per minute come out of the profile vault
+            Check::account(&profile_vault_pda).lamports(profile_vault_balance - 1_000).build(),
+            Check::account(&vault_pda).lamports(vault_balance).build(),
+            // 10% platform cut, the rest to the tenant
+            Check::account(&platform_treasury.pubkey()).lamports(mollusk.sysvars.rent.minimum_balance(0) + 100).build(),
+            Check::account(&revenue_pda).lamports(mollusk.sysvars.rent.minimum_balance(0) + 900).build(),
+        ],
+    );
+
+    let profile_state: Profile = read_result(&result, &profile_pda);
+    assert_eq!(profile_state.amount, profile_vault_balance - 1_000);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert!(!user_state.is_parked);
+    assert_eq!(user_state.last_fee_payer, FeePayer::Profile);
+    assert_eq!(user_state.last_tenant_amount, 900);
+}
+#[test]
+fn test_flag_overstay() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mut mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    // Two hours after the car parked
+    mollusk.sysvars.clock.unix_timestamp = 7_200;
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let enforcer = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let enforcer_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let user_wallet_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account with a one hour maximum stay
+    use parkat_anchor::state::{Tenant, User};
+
+    let max_duration_seconds: u64 = 3_600;
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.max_duration_seconds = max_duration_seconds;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account - parked since time 0
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.is_parked = true;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(enforcer.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(user_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::FlagOverstay {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (enforcer.pubkey(), enforcer_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user.pubkey(), user_wallet_account),
+        (user_pda, user_pda_account),
+    ];
+
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_open_dispute() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let sponsor = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // The dispute references the user's first finalized session
+    let session_id: u64 = 1;
+    let (dispute_pda, _dispute_bump) = Pubkey::find_program_address(
+        &[b"dispute", user_pda.as_ref(), &session_id.to_le_bytes()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(0, 0, &system_program);
+    let sponsor_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    use parkat_anchor::state::{Dispute, FeePayer, Tenant, User};
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account with one finalized session charged to their fleet,
+    // of which the platform kept 10%
+    let fee: u64 = 600;
+    let tenant_amount: u64 = 540;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.session_count = session_id;
+    user_state.last_session.fee = fee;
+    user_state.last_fee_payer = FeePayer::Fleet;
+    user_state.last_tenant_amount = tenant_amount;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let dispute_account = Account::new(0, 0, &system_program);
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(sponsor.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(user_pda, false),
+        AccountMeta::new(dispute_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::OpenDispute {
+        reason: String::from("Exit gate did not open"),
+    }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (sponsor.pubkey(), sponsor_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_pda, user_pda_account),
+        (dispute_pda, dispute_account),
+        (system_program, system_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+
+    // The dispute remembers who paid and how much reached the tenant
+    let dispute_state: Dispute = read_result(&result, &dispute_pda);
+    assert_eq!(dispute_state.fee, fee);
+    assert_eq!(dispute_state.payer, FeePayer::Fleet);
+    assert_eq!(dispute_state.tenant_amount, tenant_amount);
+
+    // The tenant's share is held back from payouts while the dispute is open
+    let tenant_state: Tenant = read_result(&result, &tenant_pda);
+    assert_eq!(tenant_state.disputed_amount, tenant_amount);
+}
+
+/// Resolves a dispute over a 600 lamport fee paid by the user's fleet, of which
+/// the tenant kept 540, refunding `refund`
+fn resolve_fleet_dispute(refund: u64, checks: &[Check]) {
+    use parkat_anchor::state::{Dispute, DisputeStatus, FeePayer, Fleet, FleetMember, Tenant, User};
+
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let fleet_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+    let session_id: u64 = 1;
+    let (dispute_pda, dispute_bump) = Pubkey::find_program_address(
+        &[b"dispute", user_pda.as_ref(), &session_id.to_le_bytes()],
+        &program_id
+    );
+    let (fleet_pda, fleet_bump) = Pubkey::find_program_address(
+        &[b"fleet", tenant_pda.as_ref(), fleet_admin.pubkey().as_ref()],
+        &program_id
+    );
+    let (fleet_vault_pda, fleet_vault_bump) = Pubkey::find_program_address(
+        &[b"fleet_vault", fleet_pda.as_ref()],
+        &program_id
+    );
+    let (fleet_member_pda, fleet_member_bump) = Pubkey::find_program_address(
+        &[b"fleet_member", fleet_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let rent_reserve = mollusk.sysvars.rent.minimum_balance(0);
+    let fee: u64 = 600;
+    let tenant_amount: u64 = 540;
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+    tenant_state.total_collected = tenant_amount;
+    tenant_state.disputed_amount = tenant_amount;
+
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.session_count = session_id;
+
+    let mut dispute_state: Dispute = zeroed();
+    dispute_state.tenant = key(&tenant_pda);
+    dispute_state.user = key(&user.pubkey());
+    dispute_state.session_id = session_id;
+    dispute_state.fee = fee;
+    dispute_state.payer = FeePayer::Fleet;
+    dispute_state.tenant_amount = tenant_amount;
+    dispute_state.status = DisputeStatus::Open;
+    dispute_state.bump = dispute_bump;
+
+    let mut fleet_state: Fleet = zeroed();
+    fleet_state.admin = key(&fleet_admin.pubkey());
+    fleet_state.tenant = key(&tenant_pda);
+    fleet_state.bump = fleet_bump;
+    fleet_state.vault_bump = fleet_vault_bump;
+
+    let mut fleet_member_state: FleetMember = zeroed();
+    fleet_member_state.fleet = key(&fleet_pda);
+    fleet_member_state.user = key(&user.pubkey());
+    fleet_member_state.monthly_limit = LAMPORTS_PER_SOL;
+    fleet_member_state.spent_this_period = fee;
+    fleet_member_state.bump = fleet_member_bump;
+
+    // Optional profile accounts are skipped with the program id as placeholder
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(tenant_admin.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new(dispute_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+        AccountMeta::new_readonly(fleet_pda, false),
+        AccountMeta::new(fleet_vault_pda, false),
+        AccountMeta::new(fleet_member_pda, false),
+        AccountMeta::new_readonly(program_id, false),
+        AccountMeta::new_readonly(program_id, false),
+    ];
+
+    let data = parkat_anchor::instruction::ResolveDispute { refund }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (revenue_pda, Account::new(rent_reserve + tenant_amount, 0, &system_program)),
+        (user.pubkey(), Account::new(0, 0, &system_program)),
+        (vault_pda, Account::new(rent_reserve, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (dispute_pda, program_account(&mollusk, &program_id, &dispute_state)),
+        (system_program, system_account),
+        (fleet_pda, program_account(&mollusk, &program_id, &fleet_state)),
+        (fleet_vault_pda, Account::new(rent_reserve, 0, &system_program)),
+        (fleet_member_pda, program_account(&mollusk, &program_id, &fleet_member_state)),
+        (program_id, program::create_program_account_loader_v3(&program_id)),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    if result.program_result.is_ok() {
+        // The fleet gets its money back, the user's vault is untouched
+        assert_eq!(result.get_account(&fleet_vault_pda).unwrap().lamports, rent_reserve + refund);
+        assert_eq!(result.get_account(&vault_pda).unwrap().lamports, rent_reserve);
+
+        let fleet_member_state: FleetMember = read_result(&result, &fleet_member_pda);
+        assert_eq!(fleet_member_state.spent_this_period, fee - refund);
+
+        // The unrefunded part of the tenant's share is released for payouts again
+        let tenant_state: Tenant = read_result(&result, &tenant_pda);
+        assert_eq!(tenant_state.disputed_amount, 0);
+    }
+}
+
+#[test]
+fn test_resolve_dispute_refunds_fleet() {
+    resolve_fleet_dispute(540, &[Check::success()]);
+}
+
+#[test]
+fn test_resolve_dispute_capped_at_tenant_share() {
+    // The platform's 60 lamport cut never reached the tenant's revenue
+    resolve_fleet_dispute(
+        541,
+        &[Check::err(ProgramError::Custom(RESOLVE_DISPUTE_REFUND_EXCEEDS_TENANT_SHARE))],
+    );
+}
+
+
+/// Distributes 10_000 collected lamports 70/30, of which `disputed_amount` and
+/// `roaming_payable` are held back
+fn distribute_revenue(disputed_amount: u64, roaming_payable: u64, checks: &[Check]) {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let tenant_admin = Keypair::new();
+    let landowner = Keypair::new();
+    let security_contractor = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+        &[b"revenue", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (payout_split_pda, payout_split_bump) = Pubkey::find_program_address(
+        &[b"payout_split", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let landowner_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let security_contractor_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Revenue PDA holds its rent-exempt reserve plus collected fees
+    let collected_fees: u64 = 10_000;
+    let revenue_account = Account::new(
+        mollusk.sysvars.rent.minimum_balance(0) + collected_fees,
+        0,
+        &system_program,
+    );
+
+    // Create tenant account
+    use parkat_anchor::state::Tenant;
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.revenue_bump = revenue_bump;
+    tenant_state.disputed_amount = disputed_amount;
+    tenant_state.roaming_payable = roaming_payable;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create payout split: landowner 70%, security contractor 30%
+    let payout_split_state = PayoutSplit {
+        tenant: key(&tenant_pda),
+        recipients: vec![
+            PayoutRecipient { wallet: key(&landowner.pubkey()), share_bps: 7_000 },
+            PayoutRecipient { wallet: key(&security_contractor.pubkey()), share_bps: 3_000 },
+        ],
+        bump: payout_split_bump,
+    };
+
+    let payout_split_account = program_account(&mollusk, &program_id, &payout_split_state);
+
+    // Recipient wallets follow the fixed accounts, in payout split order
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(revenue_pda, false),
+        AccountMeta::new_readonly(payout_split_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+        AccountMeta::new(landowner.pubkey(), false),
+        AccountMeta::new(security_contractor.pubkey(), false),
+    ];
+
+    let data = parkat_anchor::instruction::DistributeRevenue {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (revenue_pda, revenue_account),
+        (payout_split_pda, payout_split_account),
+        (system_program, system_account),
+        (landowner.pubkey(), landowner_account),
+        (security_contractor.pubkey(), security_contractor_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    if result.program_result.is_ok() {
+        // Only revenue nobody else can still claim is paid out
+        let distributable = collected_fees - disputed_amount - roaming_payable;
+        assert_eq!(
+            result.get_account(&landowner.pubkey()).unwrap().lamports,
+            LAMPORTS_PER_SOL + distributable * 7 / 10,
+        );
+        assert_eq!(
+            result.get_account(&security_contractor.pubkey()).unwrap().lamports,
+            LAMPORTS_PER_SOL + distributable * 3 / 10,
+        );
+    }
+}
+
+#[test]
+fn test_distribute_revenue() {
+    distribute_revenue(0, 0, &[Check::success()]);
+}
+
+#[test]
+fn test_distribute_revenue_holds_back_disputes_and_roaming() {
+    distribute_revenue(4_000, 2_000, &[Check::success()]);
+}
+
+#[test]
+fn test_distribute_revenue_fully_reserved() {
+    distribute_revenue(
+        6_000,
+        4_000,
+        &[Check::err(ProgramError::Custom(DISTRIBUTE_REVENUE_NOTHING_TO_DISTRIBUTE))],
+    );
+}
+
+/// Gives the tenant a single revenue recipient with `InitPayoutSplit` when `init` is set and
+/// `SetPayoutSplit` otherwise. `payout_split` lists the recipients of an existing split.
+fn payout_split(
+    init: bool,
+    payout_split: Option<Vec<PayoutRecipient>>,
+    checks: &[Check],
+) -> (Pubkey, InstructionResult) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let admin = Keypair::new();
+    let (tenant_pda, tenant_state) = tenant_fixture(&admin.pubkey());
+    let (payout_split_pda, payout_split_bump) = Pubkey::find_program_address(
+        &[b"payout_split", tenant_pda.as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let payout_split_account = match payout_split {
+        Some(recipients) => program_account(&mollusk, &program_id, &PayoutSplit {
+            tenant: key(&tenant_pda),
+            recipients,
+            bump: payout_split_bump,
+        }),
+        None => Account::default(),
+    };
+
+    let recipients = vec![PayoutRecipient { wallet: key(&Pubkey::new_unique()), share_bps: 10_000 }];
+
+    let mut instruction_accounts = vec![
+        AccountMeta::new(admin.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new(payout_split_pda, false),
+    ];
+    let mut tx_accounts = vec![
+        (admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (payout_split_pda, payout_split_account),
+    ];
+
+    let data = if init {
+        instruction_accounts.push(AccountMeta::new_readonly(system_program, false));
+        tx_accounts.push((system_program, system_account));
+        parkat_anchor::instruction::InitPayoutSplit { recipients }.data()
+    } else {
+        parkat_anchor::instruction::SetPayoutSplit { recipients }.data()
+    };
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+    (payout_split_pda, result)
+}
+
+#[test]
+fn test_init_payout_split() {
+    let (payout_split_pda, result) = payout_split(true, None, &[Check::success()]);
+
+    let payout_split_state: PayoutSplit = read_result(&result, &payout_split_pda);
+    assert_eq!(payout_split_state.recipients.len(), 1);
+}
+
+#[test]
+fn test_init_payout_split_twice() {
+    // An existing split can only be changed through `set_payout_split`
+    let existing = vec![PayoutRecipient { wallet: key(&Pubkey::new_unique()), share_bps: 10_000 }];
+    payout_split(
+        true,
+        Some(existing),
+        &[Check::err(ProgramError::Custom(SystemError::AccountAlreadyInUse as u32))],
+    );
+}
+
+#[test]
+fn test_set_payout_split() {
+    let existing = vec![
+        PayoutRecipient { wallet: key(&Pubkey::new_unique()), share_bps: 5_000 },
+        PayoutRecipient { wallet: key(&Pubkey::new_unique()), share_bps: 5_000 },
+    ];
+    let (payout_split_pda, result) = payout_split(false, Some(existing), &[Check::success()]);
+
+    let payout_split_state: PayoutSplit = read_result(&result, &payout_split_pda);
+    assert_eq!(payout_split_state.recipients.len(), 1);
+    assert_eq!(payout_split_state.recipients[0].share_bps, 10_000);
+}
+
+#[test]
+fn test_set_payout_split_without_split() {
+    payout_split(
+        false,
+        None,
+        &[Check::err(ProgramError::Custom(ACCOUNT_NOT_INITIALIZED))],
+    );
+}
+
+#[test]
+fn test_withdraw_by_user_while_paused() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account - paused, but withdrawals stay open
+    use parkat_anchor::state::{Tenant, User};
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+    tenant_state.paused = true;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account - not parked, with a funded vault
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.amount = vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let vault_account = Account::new(vault_balance, 0, &system_program);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::Withdraw {
+        amount: vault_balance,
+    }.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+        (system_program, system_account),
+    ];
+
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_reconcile_vault() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (system_program, _system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(0, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    use parkat_anchor::state::{Tenant, User};
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account whose ledger tracks 2 SOL of deposits
+    let vault_balance: u64 = 2 * LAMPORTS_PER_SOL;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.amount = vault_balance;
+    user_state.total_deposited = vault_balance;
+    user_state.available_balance = vault_balance;
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Someone sent lamports straight to the rent-exempt vault
+    let external_transfer: u64 = 500_000;
+    let vault_account = Account::new(
+        mollusk.sysvars.rent.minimum_balance(0) + vault_balance + external_transfer,
+        0,
+        &system_program,
+    );
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new_readonly(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+    ];
+
+    let data = parkat_anchor::instruction::ReconcileVault {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user.pubkey(), user_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+    ];
+
+    // The external transfer is credited as a deposit
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.total_deposited, vault_balance + external_transfer);
+    assert_eq!(user_state.available_balance, vault_balance + external_transfer);
+}
+
+#[test]
+fn test_record_parking_start_by_delegate() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let gate_device = Keypair::new();
+
+    // Derive tenant state PDA
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    // Derive User PDA
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // Derive Vault PDA (not used in this instruction but needed for User account data)
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // System program account
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Initialize Accounts
+    let gate_device_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account with proper discriminator
+    use parkat_anchor::state::{PlatformConfig, Tenant, User};
+    
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account with proper discriminator
+    // User is NOT parked initially (is_parked = false) and has a delegate
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    // time_stamp = 0 (positions 72-79)
+    user_state.is_parked = false;
+    // amount = 0 (positions 81-88)
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    // number_plate (positions 91-106)
+    // Gate device registered as delegate for the next hour
+    user_state.delegate = key(&gate_device.pubkey());
+    user_state.delegate_expires_at = 3_600;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Platform config (not paused)
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Build instruction accounts 
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(gate_device.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),                        
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),    
+        AccountMeta::new(user_pda, false),                          
+        AccountMeta::new_readonly(platform_config_pda, false),
+    ];
+
+    // Create instruction data 
+    let data = parkat_anchor::instruction::RecordParkingStart { timestamp: None }.data();
+
+    // Create the instruction
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (gate_device.pubkey(), gate_device_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
+        (system_program, system_account),
+    ];
+
+    // Process with the delegate as signer
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_deposit_for_user() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let employer = Keypair::new();
+
+    // Derive tenant state PDA
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    // Derive User PDA 
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // Derive Vault PDA
+    let (vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // System program account
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    // Initialize Accounts
+    let employer_account = Account::new(10 * LAMPORTS_PER_SOL, 0, &system_program);
+    let user_account = Account::new(0, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    use parkat_anchor::state::{PlatformConfig, Tenant, User};
+    
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account
+    // User layout: user(32) + tenant(32) + time_stamp(8) + is_parked(1) + amount(8) + vault_bump(1) + state_bump(1) + number_plate(16), remaining fields default to zero
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    // time_stamp = 0 (already zeros at positions 72-79)
+    // is_parked = false (already 0 at position 80)
+    // amount = 0 (already zeros at positions 81-88)
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    // number_plate is zeros (already zeros at positions 91-106)
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    // Vault account (starts with 0 lamports, will receive deposit)
+    let vault_account = Account::new(0, 0, &system_program);
+
+    // Platform config (not paused)
+    let (platform_config_pda, platform_config_bump) = Pubkey::find_program_address(
+        &[b"platform"],
+        &program_id
+    );
+
+    let mut platform_config_state: PlatformConfig = zeroed();
+    platform_config_state.bump = platform_config_bump;
+
+    let platform_config_account = program_account(&mollusk, &program_id, &platform_config_state);
+
+    // Build instruction accounts (order must match DepositFor struct)
+    let instruction_accounts = vec![
+        AccountMeta::new(employer.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(user.pubkey(), false),
+        AccountMeta::new(vault_pda, false),                 
+        AccountMeta::new(user_pda, false),                  
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),   
+    ];
+
+    // Create instruction data
+    let deposit_amount = LAMPORTS_PER_SOL;
+    let data = parkat_anchor::instruction::DepositFor {
+        amount: deposit_amount,
+    }.data();
+
+    // Create the instruction
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (employer.pubkey(), employer_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user.pubkey(), user_account),
+        (vault_pda, vault_account),
+        (user_pda, user_pda_account),
+        (platform_config_pda, platform_config_account),
+        (system_program, system_account),
+    ];
+
+    // Process with the employer as signer
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[Check::success()],
+    );
+}
+
+#[test]
+fn test_settle_roaming() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    use parkat_anchor::state::{RoamingAgreement, Tenant};
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+    let rent_reserve = mollusk.sysvars.rent.minimum_balance(0);
+
+    // Two tenants, ordered by key as the agreement requires
+    let mut tenants: Vec<(Keypair, Pubkey, u8)> = (0..2)
+        .map(|_| {
+            let admin = Keypair::new();
+            let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+                &[b"tenant", admin.pubkey().as_ref()],
+                &program_id
+            );
+            (admin, tenant_pda, tenant_bump)
+        })
+        .collect();
+    tenants.sort_by_key(|(_, tenant_pda, _)| *tenant_pda);
+
+    // A's members ran up 5_000 at B, B's members 2_000 at A
+    let collected_fees: u64 = 10_000;
+    let roaming_payables: [u64; 2] = [5_000, 2_000];
+    let mut accounts = Vec::new();
+    for ((admin, tenant_pda, tenant_bump), roaming_payable) in tenants.iter().zip(roaming_payables) {
+        let (revenue_pda, revenue_bump) = Pubkey::find_program_address(
+            &[b"revenue", tenant_pda.as_ref()],
+            &program_id
+        );
+
+        let mut tenant_state: Tenant = zeroed();
+        tenant_state.admin = key(&admin.pubkey());
+        tenant_state.bump = *tenant_bump;
+        tenant_state.revenue_bump = revenue_bump;
+        tenant_state.total_collected = collected_fees;
+        tenant_state.roaming_payable = roaming_payable;
+
+        let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+        let revenue_account = Account::new(rent_reserve + collected_fees, 0, &system_program);
+
+        accounts.push(((*tenant_pda, tenant_account), (revenue_pda, revenue_account)));
+    }
+    let ((tenant_a_pda, tenant_a_account), (revenue_a_pda, revenue_a_account)) = accounts.remove(0);
+    let ((tenant_b_pda, tenant_b_account), (revenue_b_pda, revenue_b_account)) = accounts.remove(0);
+
+    let (roaming_pda, roaming_bump) = Pubkey::find_program_address(
+        &[b"roaming", tenant_a_pda.as_ref(), tenant_b_pda.as_ref()],
+        &program_id
+    );
+    let mut roaming_state: RoamingAgreement = zeroed();
+    roaming_state.tenant_a = key(&tenant_a_pda);
+    roaming_state.tenant_b = key(&tenant_b_pda);
+    roaming_state.a_owes_b = roaming_payables[0];
+    roaming_state.b_owes_a = roaming_payables[1];
+    roaming_state.bump = roaming_bump;
+
+    let roaming_account = program_account(&mollusk, &program_id, &roaming_state);
+
+    let instruction_accounts = vec![
+        AccountMeta::new(tenant_a_pda, false),
+        AccountMeta::new(tenant_b_pda, false),
+        AccountMeta::new(revenue_a_pda, false),
+        AccountMeta::new(revenue_b_pda, false),
+        AccountMeta::new(roaming_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::SettleRoaming {}.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (tenant_a_pda, tenant_a_account),
+        (tenant_b_pda, tenant_b_account),
+        (revenue_a_pda, revenue_a_account),
+        (revenue_b_pda, revenue_b_account),
+        (roaming_pda, roaming_account),
+        (system_program, system_account),
+    ];
+
+    // Only the 3_000 net balance moves from A to B
+    let result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&revenue_a_pda).lamports(rent_reserve + collected_fees - 3_000).build(),
+            Check::account(&revenue_b_pda).lamports(rent_reserve + collected_fees + 3_000).build(),
+        ],
+    );
+
+    // Neither tenant has to hold anything back for roaming any more
+    let tenant_a_state: Tenant = read_result(&result, &tenant_a_pda);
+    let tenant_b_state: Tenant = read_result(&result, &tenant_b_pda);
+    assert_eq!(tenant_a_state.roaming_payable, 0);
+    assert_eq!(tenant_b_state.roaming_payable, 0);
+}
+
+#[test]
+fn test_issue_receipt() {
+    let program_id = Pubkey::new_from_array(
+        five8_const::decode_32_const("CJbYiHnNrzYe7imm54hYA9HiJS1Q8BJs5okxFJbhuUx3")
+    );
+
+    let mollusk = Mollusk::new(&program_id, "../../target/deploy/parkat_anchor");
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+    let sponsor = Keypair::new();
+
+    let (tenant_pda, tenant_bump) = Pubkey::find_program_address(
+        &[b"tenant", tenant_admin.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (user_pda, user_bump) = Pubkey::find_program_address(
+        &[b"user", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    let (_vault_pda, vault_bump) = Pubkey::find_program_address(
+        &[b"vault", tenant_pda.as_ref(), user.pubkey().as_ref()],
+        &program_id
+    );
+
+    // The receipt covers the user's first finalized session
+    let session_id: u64 = 1;
+    let (receipt_pda, receipt_bump) = Pubkey::find_program_address(
+        &[b"receipt", user_pda.as_ref(), &session_id.to_le_bytes()],
+        &program_id
+    );
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let user_account = Account::new(0, 0, &system_program);
+    let sponsor_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+    let tenant_admin_account = Account::new(LAMPORTS_PER_SOL, 0, &system_program);
+
+    // Create tenant account
+    use anchor_lang::AccountSerialize;
+    use parkat_anchor::state::{Receipt, Tenant, User};
+
+    let mut tenant_name = [0u8; 32];
+    tenant_name[..9].copy_from_slice(b"Riverside");
+    let mut number_plate = [0u8; 16];
+    number_plate[..7].copy_from_slice(b"ABC-123");
+
+    let mut tenant_state: Tenant = zeroed();
+    tenant_state.admin = key(&tenant_admin.pubkey());
+    tenant_state.name = tenant_name;
+    tenant_state.bump = tenant_bump;
+
+    let tenant_account = program_account(&mollusk, &program_id, &tenant_state);
+
+    // Create User account with one finalized, charged session
+    let start_time: i64 = 1_000;
+    let end_time: i64 = 1_360;
+    let fee: u64 = 600;
+    let mut user_state: User = zeroed();
+    user_state.user = key(&user.pubkey());
+    user_state.tenant = key(&tenant_pda);
+    user_state.vault_bump = vault_bump;
+    user_state.state_bump = user_bump;
+    user_state.number_plate = number_plate;
+    user_state.session_count = session_id;
+    user_state.last_session.start_time = start_time;
+    user_state.last_session.end_time = end_time;
+    user_state.last_session.fee = fee;
+
+    let user_pda_account = program_account(&mollusk, &program_id, &user_state);
+
+    let receipt_account = Account::new(0, 0, &system_program);
+
+    // Expected receipt contents, including the hash commitment
+    let to_anchor = |key: &Pubkey| anchor_lang::prelude::Pubkey::new_from_array(key.to_bytes());
+    let mut expected = Receipt {
+        tenant: to_anchor(&tenant_pda),
+        user: to_anchor(&user.pubkey()),
+        user_account: to_anchor(&user_pda),
+        tenant_name,
+        number_plate,
+        start_time,
+        end_time,
+        fee,
+        receipt_number: session_id,
+        commitment: [0u8; 32],
+        issued_at: 0,
+        bump: receipt_bump,
+    };
+    expected.commitment = expected.compute_commitment();
+    let mut expected_data = Vec::new();
+    expected.try_serialize(&mut expected_data).unwrap();
+
+    let instruction_accounts = vec![
+        AccountMeta::new_readonly(user.pubkey(), true),
+        AccountMeta::new(sponsor.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new_readonly(user_pda, false),
+        AccountMeta::new(receipt_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::IssueReceipt {}.data();
+
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), user_account),
+        (sponsor.pubkey(), sponsor_account),
+        (tenant_pda, tenant_account),
+        (tenant_admin.pubkey(), tenant_admin_account),
+        (user_pda, user_pda_account),
+        (receipt_pda, receipt_account),
+        (system_program, system_account),
+    ];
+
+    let _result = mollusk.process_and_validate_instruction(
+        &instruction,
+        &tx_accounts,
+        &[
+            Check::success(),
+            Check::account(&receipt_pda).data(&expected_data).build(),
+        ],
+    );
+}
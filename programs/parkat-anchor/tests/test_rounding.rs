@@ -0,0 +1,105 @@
+use anchor_lang::{ AnchorDeserialize, Space };
+use parkat_anchor::state::{ OverstayPenalty, RoundingMode, Tenant, UserClass, RATE_PER_MINUTE };
+
+const ROUNDING_MODES: [RoundingMode; 4] = [
+    RoundingMode::FloorMinute,
+    RoundingMode::CeilMinute,
+    RoundingMode::NearestMinute,
+    RoundingMode::PerSecond,
+];
+
+/// Tenant with every field at its default, billing with `rounding_mode`
+fn tenant_with(rounding_mode: RoundingMode) -> Tenant {
+    let mut tenant = Tenant::deserialize(&mut &vec![0u8; Tenant::INIT_SPACE][..]).unwrap();
+    tenant.rounding_mode = rounding_mode;
+    tenant
+}
+
+#[test]
+fn test_rounding_modes_bill_partial_minutes() {
+    // (duration, floor, ceil, nearest, per second)
+    let cases: [(u64, u64, u64, u64, u64); 6] = [
+        (0, 0, 0, 0, 0),
+        (29, 0, 100, 0, 48),
+        (30, 0, 100, 100, 50),
+        (59, 0, 100, 100, 98),
+        (60, 100, 100, 100, 100),
+        (119, 100, 200, 200, 198),
+    ];
+
+    for (duration, floor, ceil, nearest, per_second) in cases {
+        let expected = [floor, ceil, nearest, per_second];
+        for (mode, expected_fee) in ROUNDING_MODES.iter().zip(expected) {
+            let fee = tenant_with(*mode).parking_fee(duration, UserClass::Standard).unwrap();
+            assert_eq!(fee, expected_fee, "{mode:?} billed {duration}s");
+        }
+    }
+}
+
+#[test]
+fn test_longer_stay_never_costs_less() {
+    let classes = [
+        UserClass::Standard,
+        UserClass::Discounted { multiplier_bps: 5_000 },
+        UserClass::Exempt,
+    ];
+    let penalties = [
+        OverstayPenalty::None,
+        OverstayPenalty::Flat { amount: 1_000 },
+        OverstayPenalty::Multiplier { multiplier_bps: 15_000 },
+    ];
+
+    for mode in ROUNDING_MODES {
+        for penalty in penalties {
+            let mut tenant = tenant_with(mode);
+            tenant.max_duration_seconds = 3_600;
+            tenant.overstay_penalty = penalty;
+
+            for class in classes {
+                let mut previous = 0;
+                // Every second of the first three hours, across the overstay boundary
+                for duration in 0..=3 * 3_600 {
+                    let fee = tenant.parking_fee(duration, class).unwrap();
+                    assert!(
+                        fee >= previous,
+                        "{mode:?} {penalty:?} {class:?}: {duration}s cost {fee}, less than {previous}"
+                    );
+                    previous = fee;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rounding_modes_are_ordered() {
+    // Floor never bills more than per-second, which never bills more than ceil
+    for duration in 0..=3_600 {
+        let fee = |mode| tenant_with(mode).parking_fee(duration, UserClass::Standard).unwrap();
+        let floor = fee(RoundingMode::FloorMinute);
+        let per_second = fee(RoundingMode::PerSecond);
+        let nearest = fee(RoundingMode::NearestMinute);
+        let ceil = fee(RoundingMode::CeilMinute);
+
+        assert!(floor <= per_second && per_second <= ceil, "{duration}s");
+        assert!(floor <= nearest && nearest <= ceil, "{duration}s");
+    }
+}
+
+#[test]
+fn test_full_minutes_bill_the_same_in_every_mode() {
+    for minutes in 0..=120u64 {
+        for mode in ROUNDING_MODES {
+            let fee = tenant_with(mode).parking_fee(minutes * 60, UserClass::Standard).unwrap();
+            assert_eq!(fee, minutes * RATE_PER_MINUTE, "{mode:?} billed {minutes} minutes");
+        }
+    }
+}
+
+#[test]
+fn test_huge_durations_do_not_panic() {
+    for mode in ROUNDING_MODES {
+        let tenant = tenant_with(mode);
+        let _ = tenant.parking_fee(u64::MAX, UserClass::Standard);
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::{ AnchorDeserialize, Space };
+use parkat_anchor::state::{ Tenant, TimingMode, MS_PER_SLOT };
+
+const START: i64 = 1_700_000_000;
+
+/// Tenant with every field at its default, measuring sessions with `timing_mode`
+fn tenant_with(timing_mode: TimingMode, clock_skew_tolerance_seconds: u32) -> Tenant {
+    let mut tenant = Tenant::deserialize(&mut &vec![0u8; Tenant::INIT_SPACE][..]).unwrap();
+    tenant.timing_mode = timing_mode;
+    tenant.clock_skew_tolerance_seconds = clock_skew_tolerance_seconds;
+    tenant
+}
+
+#[test]
+fn test_wall_clock_duration() {
+    let tenant = tenant_with(TimingMode::WallClock, 0);
+
+    assert_eq!(tenant.session_duration(START, 0, START, 0), Some(0));
+    assert_eq!(tenant.session_duration(START, 0, START + 90, 0), Some(90));
+}
+
+#[test]
+fn test_negative_duration_within_tolerance_is_zero() {
+    let tenant = tenant_with(TimingMode::WallClock, 30);
+
+    assert_eq!(tenant.session_duration(START, 0, START - 1, 0), Some(0));
+    assert_eq!(tenant.session_duration(START, 0, START - 30, 0), Some(0));
+    assert_eq!(tenant.session_duration(START, 0, START - 31, 0), None);
+}
+
+#[test]
+fn test_negative_duration_rejected_without_tolerance() {
+    let tenant = tenant_with(TimingMode::WallClock, 0);
+
+    assert_eq!(tenant.session_duration(START, 0, START - 1, 0), None);
+}
+
+#[test]
+fn test_slot_mode_ignores_wall_clock() {
+    let tenant = tenant_with(TimingMode::Slot, 0);
+    let start_slot = 1_000;
+    let slots = 150;
+
+    // The wall clock ran backwards, but slots kept advancing
+    let duration = tenant.session_duration(START, start_slot, START - 120, start_slot + slots);
+    assert_eq!(duration, Some(slots * MS_PER_SLOT / 1_000));
+}
+
+#[test]
+fn test_slot_mode_falls_back_to_wall_clock_without_start_slot() {
+    // Sessions started before slot timing was enabled, or adjusted by the admin
+    let tenant = tenant_with(TimingMode::Slot, 0);
+
+    assert_eq!(tenant.session_duration(START, 0, START + 60, 5_000), Some(60));
+}
+
+#[test]
+fn test_slot_mode_rejects_earlier_slot() {
+    let tenant = tenant_with(TimingMode::Slot, 600);
+
+    assert_eq!(tenant.session_duration(START, 1_000, START + 60, 999), None);
+}
@@ -0,0 +1,207 @@
+#[cfg(test)]
+use mollusk_svm::{ program, result::{ Check, InstructionResult } };
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{ Keypair, Signer },
+    instruction::{ AccountMeta, Instruction },
+    account::Account,
+    native_token::LAMPORTS_PER_SOL,
+    program_error::ProgramError,
+};
+use anchor_lang::{ error::ERROR_CODE_OFFSET, InstructionData };
+use parkat_anchor::instructions::{ DepositError, WithdrawError };
+use parkat_anchor::state::User;
+
+mod common;
+use common::{
+    mollusk,
+    platform_config_fixture,
+    program_account,
+    program_id,
+    read_result,
+    tenant_fixture,
+    user_fixture,
+};
+
+// Anchor custom error codes
+const DEPOSIT_BELOW_RENT_EXEMPTION: u32 =
+    DepositError::DepositBelowRentExemption as u32 + ERROR_CODE_OFFSET;
+const WITHDRAW_INSUFFICIENT_VAULT_BALANCE: u32 =
+    WithdrawError::InsufficientVaultBalance as u32 + ERROR_CODE_OFFSET;
+const WITHDRAW_BELOW_RENT_RESERVE: u32 = WithdrawError::BelowRentReserve as u32 + ERROR_CODE_OFFSET;
+
+/// Lamports a zero-data vault must hold to be rent exempt
+fn rent_reserve() -> u64 {
+    mollusk().sysvars.rent.minimum_balance(0)
+}
+
+/// Deposits `amount` into a vault holding `vault_lamports`, returning the vault and user PDAs
+fn deposit(vault_lamports: u64, amount: u64, checks: &[Check]) -> (Pubkey, Pubkey, InstructionResult) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state) = tenant_fixture(&tenant_admin.pubkey());
+    let (user_pda, vault_pda, user_state) = user_fixture(&tenant_pda, &user.pubkey());
+    let (platform_config_pda, platform_config_state) = platform_config_fixture();
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(platform_config_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::Deposit { amount }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(vault_lamports, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (platform_config_pda, program_account(&mollusk, &program_id, &platform_config_state)),
+        (system_program, system_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    (vault_pda, user_pda, result)
+}
+
+/// Withdraws `amount` from a vault holding `vault_lamports` whose ledger says
+/// `available_balance` lamports can be spent, returning the vault and user PDAs
+fn withdraw(
+    vault_lamports: u64,
+    available_balance: u64,
+    amount: u64,
+    checks: &[Check]
+) -> (Pubkey, Pubkey, InstructionResult) {
+    let program_id = program_id();
+    let mollusk = mollusk();
+
+    let user = Keypair::new();
+    let tenant_admin = Keypair::new();
+
+    let (tenant_pda, tenant_state) = tenant_fixture(&tenant_admin.pubkey());
+    let (user_pda, vault_pda, mut user_state) = user_fixture(&tenant_pda, &user.pubkey());
+    user_state.available_balance = available_balance;
+
+    let (system_program, system_account) = program::keyed_account_for_system_program();
+
+    let instruction_accounts = vec![
+        AccountMeta::new(user.pubkey(), true),
+        AccountMeta::new_readonly(tenant_pda, false),
+        AccountMeta::new_readonly(tenant_admin.pubkey(), false),
+        AccountMeta::new(vault_pda, false),
+        AccountMeta::new(user_pda, false),
+        AccountMeta::new_readonly(system_program, false),
+    ];
+
+    let data = parkat_anchor::instruction::Withdraw { amount }.data();
+    let instruction = Instruction::new_with_bytes(program_id, &data, instruction_accounts);
+
+    let tx_accounts = vec![
+        (user.pubkey(), Account::new(0, 0, &system_program)),
+        (tenant_pda, program_account(&mollusk, &program_id, &tenant_state)),
+        (tenant_admin.pubkey(), Account::new(LAMPORTS_PER_SOL, 0, &system_program)),
+        (vault_pda, Account::new(vault_lamports, 0, &system_program)),
+        (user_pda, program_account(&mollusk, &program_id, &user_state)),
+        (system_program, system_account),
+    ];
+
+    let result = mollusk.process_and_validate_instruction(&instruction, &tx_accounts, checks);
+
+    (vault_pda, user_pda, result)
+}
+
+#[test]
+fn test_one_lamport_deposit_into_funded_vault() {
+    let rent_reserve = rent_reserve();
+
+    let (vault_pda, user_pda, result) = deposit(rent_reserve, 1, &[Check::success()]);
+
+    assert_eq!(result.get_account(&vault_pda).unwrap().lamports, rent_reserve + 1);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.total_deposited, 1);
+    assert_eq!(user_state.available_balance, 1);
+}
+
+#[test]
+fn test_deposit_below_rent_exemption_into_unfunded_vault() {
+    // Vaults registered before pre-funding start empty
+    deposit(
+        0,
+        rent_reserve() - 1,
+        &[Check::err(ProgramError::Custom(DEPOSIT_BELOW_RENT_EXEMPTION))],
+    );
+}
+
+#[test]
+fn test_deposit_exactly_rent_exemption_into_unfunded_vault() {
+    let rent_reserve = rent_reserve();
+
+    let (vault_pda, _, result) = deposit(0, rent_reserve, &[Check::success()]);
+
+    assert_eq!(result.get_account(&vault_pda).unwrap().lamports, rent_reserve);
+}
+
+#[test]
+fn test_withdraw_full_balance_keeps_rent_reserve() {
+    let rent_reserve = rent_reserve();
+
+    let (vault_pda, user_pda, result) = withdraw(
+        rent_reserve + LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        &[Check::success()],
+    );
+
+    assert_eq!(result.get_account(&vault_pda).unwrap().lamports, rent_reserve);
+
+    let user_state: User = read_result(&result, &user_pda);
+    assert_eq!(user_state.available_balance, 0);
+}
+
+#[test]
+fn test_withdraw_more_than_available_balance() {
+    // The rent reserve is never part of the available balance
+    withdraw(
+        rent_reserve() + LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL + 1,
+        &[Check::err(ProgramError::Custom(WITHDRAW_INSUFFICIENT_VAULT_BALANCE))],
+    );
+}
+
+#[test]
+fn test_withdraw_leaving_dust_in_unfunded_vault() {
+    // Without a reserve the vault may be emptied, but not left holding dust
+    withdraw(
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL - 1,
+        &[Check::err(ProgramError::Custom(WITHDRAW_BELOW_RENT_RESERVE))],
+    );
+}
+
+#[test]
+fn test_withdraw_emptying_unfunded_vault() {
+    let (vault_pda, _, result) = withdraw(
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        LAMPORTS_PER_SOL,
+        &[Check::success()],
+    );
+
+    assert_eq!(result.get_account(&vault_pda).unwrap().lamports, 0);
+}